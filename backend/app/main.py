@@ -18,6 +18,26 @@ import sys
 from app.core.model_cache import configure_model_cache_environment
 
 
+def _configure_line_buffered_output() -> None:
+    """Flush stdout and stderr once per line.
+
+    The desktop shell sends each stream to its own append-mode log file and
+    tails them while the backend runs. Line buffering makes every line show
+    up whole as soon as it is complete; block-buffered stdout would arrive
+    in bursts and be flushed mid-line.
+    ``write_through`` is turned off because PYTHONUNBUFFERED (set by the
+    shell) enables it, and then ``print`` writes the text and the newline
+    separately.
+    """
+    for stream in (sys.stdout, sys.stderr):
+        reconfigure = getattr(stream, 'reconfigure', None)
+        if reconfigure is not None:
+            reconfigure(line_buffering=True, write_through=False)
+
+
+_configure_line_buffered_output()
+
+
 def _configure_bundled_app() -> None:
     """Set environment variables for bundled PyInstaller app."""
     is_frozen = getattr(sys, 'frozen', False)
@@ -160,9 +180,10 @@ async def health_check() -> dict[str, Any]:
     """Health check endpoint.
 
     Returns:
-        Status object indicating the server is running.
+        Status object indicating the server is running. The ``app`` field
+        lets the desktop shell tell this backend apart from other servers.
     """
-    return {"status": "ok"}
+    return {"status": "ok", "app": "alproj"}
 
 
 # =============================================================================
@@ -173,13 +194,20 @@ async def health_check() -> dict[str, Any]:
 def main() -> None:
     """Run the server with CLI argument support.
 
-    Supports --host and --port arguments for Tauri sidecar integration.
+    Supports --host and --port arguments for Tauri sidecar integration,
+    or --uds to listen on a Unix domain socket instead of TCP, and
+    --workers to run several worker processes.
     Falls back to settings defaults if not provided.
     """
     import argparse
+    import multiprocessing
 
     import uvicorn
 
+    # Worker processes re-run the frozen executable; this hands them off to
+    # multiprocessing instead of starting another server
+    multiprocessing.freeze_support()
+
     parser = argparse.ArgumentParser(description="alproj-gui Backend Server")
     parser.add_argument(
         "--host",
@@ -193,12 +221,24 @@ def main() -> None:
         default=settings.port,
         help=f"Server bind port (default: {settings.port})",
     )
+    parser.add_argument(
+        "--uds",
+        type=str,
+        default=None,
+        help="Listen on this Unix domain socket instead of host/port",
+    )
     parser.add_argument(
         "--reload",
         action="store_true",
         default=settings.debug,
         help="Enable auto-reload (for development)",
     )
+    parser.add_argument(
+        "--workers",
+        type=int,
+        default=1,
+        help="Number of worker processes (default: 1)",
+    )
 
     args = parser.parse_args()
 
@@ -206,7 +246,9 @@ def main() -> None:
         "app.main:app",
         host=args.host,
         port=args.port,
+        uds=args.uds,
         reload=args.reload,
+        workers=args.workers,
         log_level=settings.log_level.lower(),
     )
 
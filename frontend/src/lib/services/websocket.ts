@@ -8,10 +8,17 @@
 import type { ProcessResult } from '$lib/types';
 
 // WebSocket configuration
-const WS_BASE_URL = 'ws://127.0.0.1:8765';
+let wsBaseUrl = 'ws://127.0.0.1:8765';
 const RECONNECT_DELAY = 1000; // ms
 const MAX_RECONNECT_ATTEMPTS = 5;
 
+/**
+ * Update WebSocket base URL (e.g. when the desktop shell picks another port)
+ */
+export function setWebSocketBaseUrl(url: string): void {
+	wsBaseUrl = url;
+}
+
 /**
  * WebSocket message types from server
  */
@@ -76,7 +83,7 @@ export function connectJobWebSocket(jobId: string, callbacks: WebSocketCallbacks
 	function connect() {
 		if (isCleanedUp) return;
 
-		const url = `${WS_BASE_URL}/api/jobs/${jobId}/ws`;
+		const url = `${wsBaseUrl}/api/jobs/${jobId}/ws`;
 		ws = new WebSocket(url);
 
 		ws.onopen = () => {
@@ -61,7 +61,15 @@ case "$(uname -s)" in
         esac
         ;;
     MINGW*|MSYS*|CYGWIN*)
-        PLATFORM="x86_64-pc-windows-msvc"
+        # uname -m reports the emulated architecture under x64 emulation
+        case "${PROCESSOR_ARCHITEW6432:-$PROCESSOR_ARCHITECTURE}" in
+            ARM64)
+                PLATFORM="aarch64-pc-windows-msvc"
+                ;;
+            *)
+                PLATFORM="x86_64-pc-windows-msvc"
+                ;;
+        esac
         ;;
     *)
         echo "Error: Unknown platform"
@@ -200,6 +208,9 @@ chmod +x "$SIDECAR_DIR/$OUTPUT_NAME"
 # (Tauri looks for binaries/backend-sidecar-{platform})
 ln -sf "sidecar-$PLATFORM/$OUTPUT_NAME" "$BINARIES_DIR/$OUTPUT_NAME"
 
+# Record file hashes so the app can detect a damaged bundle
+uv run python "$SCRIPT_DIR/write-bundle-manifest.py" "$SIDECAR_DIR"
+
 # Clean up build artifacts
 rm -rf "$BACKEND_DIR/build" "$BACKEND_DIR/*.spec"
 
@@ -0,0 +1,73 @@
+#!/usr/bin/env python3
+"""
+Write the integrity manifest for a PyInstaller --onedir sidecar bundle.
+
+Records the size and SHA-256 of every file in the bundle (binary and
+_internal contents) so the desktop app can report missing or damaged files
+with `verify_backend_bundle`. Also writes `checksum.txt` with the SHA-256
+of the sidecar binary, which the app checks before every launch. Run it
+after the bundle is in its final place and the binary has its
+platform-suffixed name.
+
+Usage:
+    python scripts/write-bundle-manifest.py src-tauri/binaries/sidecar-<target>
+"""
+
+import hashlib
+import json
+import sys
+from pathlib import Path
+
+MANIFEST_NAME = "bundle-manifest.json"
+CHECKSUM_NAME = "checksum.txt"
+BINARY_PREFIX = "backend-sidecar-"
+
+
+def sha256_of(path: Path) -> str:
+    """Hash a file in chunks so large libraries don't need to fit in memory."""
+    digest = hashlib.sha256()
+    with path.open("rb") as f:
+        for chunk in iter(lambda: f.read(1024 * 1024), b""):
+            digest.update(chunk)
+    return digest.hexdigest()
+
+
+def main() -> None:
+    if len(sys.argv) != 2:
+        print(__doc__)
+        sys.exit(1)
+
+    bundle_dir = Path(sys.argv[1])
+    if not bundle_dir.is_dir():
+        print(f"Error: bundle directory not found: {bundle_dir}")
+        sys.exit(1)
+
+    files = []
+    for path in sorted(bundle_dir.rglob("*")):
+        if not path.is_file() or path.is_symlink():
+            continue
+        if path.name in (MANIFEST_NAME, CHECKSUM_NAME):
+            continue
+        files.append(
+            {
+                "path": path.relative_to(bundle_dir).as_posix(),
+                "size": path.stat().st_size,
+                "sha256": sha256_of(path),
+            }
+        )
+
+    manifest = {"version": 1, "files": files}
+    (bundle_dir / MANIFEST_NAME).write_text(json.dumps(manifest, indent=1))
+    print(f"Wrote {MANIFEST_NAME} for {len(files)} files in {bundle_dir}")
+
+    binaries = [f for f in files if f["path"].startswith(BINARY_PREFIX)]
+    if len(binaries) != 1:
+        print(f"Error: expected one {BINARY_PREFIX}* binary in {bundle_dir}")
+        sys.exit(1)
+    binary = binaries[0]
+    (bundle_dir / CHECKSUM_NAME).write_text(f"{binary['sha256']}  {binary['path']}\n")
+    print(f"Wrote {CHECKSUM_NAME} for {binary['path']}")
+
+
+if __name__ == "__main__":
+    main()
@@ -2,30 +2,283 @@
 // This module initializes the Tauri application and manages the Python sidecar
 
 use log::{error, info, warn};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::{self, OpenOptions};
 use std::io::{Read, Seek, SeekFrom};
+use std::ops::RangeInclusive;
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use sysinfo::{Pid, System};
 use tauri::async_runtime::Mutex;
 use tauri::Emitter;
 use tauri::Manager;
+use tauri_plugin_dialog::DialogExt;
 use tauri_plugin_shell::process::CommandChild;
 use tokio::time::{sleep, Duration};
 
 /// Backend configuration
 const BACKEND_HOST: &str = "127.0.0.1";
 const BACKEND_PORT: u16 = 8765;
-const HEALTH_CHECK_URL: &str = "http://127.0.0.1:8765/api/health";
-const HEALTH_CHECK_URL_LOCALHOST: &str = "http://localhost:8765/api/health";
+/// Ports after the preferred one tried when it is occupied
+const PORT_SEARCH_SPAN: u16 = 10;
+/// Spawn attempts when the backend loses its port to another process
+/// between our probe and its own bind
+const BIND_RETRY_ATTEMPTS: u32 = 3;
+const HEALTH_CHECK_PATH: &str = "/api/health";
 const HEALTH_CHECK_TIMEOUT_SECS: u64 = 180;
-const HEALTH_CHECK_INTERVAL_MS: u64 = 500;
-const BACKEND_LOG_FILE_NAME: &str = "backend-sidecar.log";
+/// Overrides `HEALTH_CHECK_TIMEOUT_SECS`, e.g. shorter for CI smoke tests or
+/// longer where the first start installs Python dependencies
+const ENV_HEALTH_TIMEOUT_SECS: &str = "ALPROJ_HEALTH_TIMEOUT_SECS";
+/// Upper bound accepted by `set_health_timeout`
+const MAX_HEALTH_CHECK_TIMEOUT_SECS: u64 = 3600;
+/// Past the health timeout, startup keeps waiting while the backend log
+/// grows (e.g. `uv run` installing dependencies on first launch) and fails
+/// once it has been quiet this long. This only applies while no health poll
+/// has been answered: once the server responds, the log may just be growing
+/// from access lines for our own polls. `MAX_HEALTH_CHECK_TIMEOUT_SECS` still
+/// caps the whole wait.
+const STARTUP_QUIET_TIMEOUT_SECS: u64 = 60;
+/// Startup health polling begins at this interval and doubles after every
+/// unanswered poll, up to `HEALTH_CHECK_MAX_INTERVAL_MS`
+const HEALTH_CHECK_INITIAL_INTERVAL_MS: u64 = 250;
+const HEALTH_CHECK_MAX_INTERVAL_MS: u64 = 2000;
+/// Each startup poll waits its interval ± this percentage, so windows or
+/// instances started together don't poll in lockstep
+const HEALTH_CHECK_JITTER_PERCENT: u64 = 20;
+/// Timeout applied to every backend HTTP request via the shared client
+const HTTP_CLIENT_TIMEOUT_SECS: u64 = 5;
+/// Per-check timeout for `run_backend_selftest`
+const SELFTEST_CHECK_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_SELFTEST_ENDPOINTS: &[&str] = &["/api/health", "/", "/openapi.json"];
+/// Optional dedicated self-test endpoint; a 404 just means it isn't provided
+const SELFTEST_DEDICATED_PATH: &str = "/api/selftest";
+/// Cached health results older than this are refreshed by `get_backend_info`
+const HEALTH_INFO_STALE_SECS: u64 = 10;
+/// How long `restart_backend_graceful` waits for in-flight requests by default
+const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 30;
+const DRAIN_POLL_INTERVAL_MS: u64 = 500;
+/// Optional backend endpoint asking it to stop accepting new work
+const DRAIN_PATH: &str = "/api/drain";
+/// How long health may fail in reload mode while the reloader parent is alive
+const RELOAD_GRACE_SECS: u64 = 30;
+/// Backend endpoint with build metadata (falls back to the health body)
+const VERSION_PATH: &str = "/api/version";
+/// Size of the fixed buffer log reads go through
+const LOG_READ_BUFFER_BYTES: usize = 16 * 1024;
+/// Cap on a `read_backend_log_chunk` read in low-memory mode
+const LOW_MEMORY_MAX_READ_BYTES: usize = 64 * 1024;
+/// Log reader handles open at once; guards against a frontend that never
+/// closes them
+const MAX_LOG_READERS: usize = 16;
+/// Most lines one `read_backend_log_lines` call returns
+const MAX_LOG_LINES_PER_READ: usize = 2000;
+/// Default and largest number of matches `search_backend_log` returns
+const LOG_SEARCH_MAX_MATCHES: usize = 500;
+/// Context lines around each `search_backend_log` match: default and cap
+const LOG_SEARCH_DEFAULT_CONTEXT: usize = 2;
+const LOG_SEARCH_MAX_CONTEXT: usize = 20;
+/// Leading bytes of the log remembered to notice it was replaced
+const LOG_INDEX_HEAD_BYTES: usize = 256;
+/// The line index keeps the start of every this many lines, so its size is
+/// a fraction of the line count; reads scan forward from the nearest one
+const LOG_INDEX_STRIDE: usize = 64;
+/// How much of the end of the log `format_log_tail` looks at
+const LOG_TAIL_MAX_BYTES: u64 = 256 * 1024;
+/// How much of the end of each log `export_diagnostics` includes
+const DIAGNOSTICS_LOG_MAX_BYTES: u64 = 8 * 1024 * 1024;
+/// Environment variable names containing any of these have their values
+/// redacted in exported diagnostics
+const SECRET_ENV_MARKERS: &[&str] = &["TOKEN", "KEY", "PASSWORD", "SECRET"];
+/// Integrity manifest written next to the sidecar by `write-bundle-manifest.py`
+const BUNDLE_MANIFEST_FILE_NAME: &str = "bundle-manifest.json";
+/// Expected SHA-256 of the sidecar binary (`sha256sum` format), written by
+/// `write-bundle-manifest.py`
+const SIDECAR_CHECKSUM_FILE_NAME: &str = "checksum.txt";
+/// Upper bound for the captured startup output returned by `get_startup_log`
+const STARTUP_LOG_MAX_BYTES: usize = 256 * 1024;
+/// Coalescing window for `backend-log-appended` events
+const LOG_STREAM_DEFAULT_INTERVAL_MS: u64 = 100;
+/// Largest batch a single `backend-log-appended` event carries; when more
+/// accumulated, the stream skips ahead and only sends the newest bytes
+const LOG_STREAM_MAX_BATCH_BYTES: usize = 256 * 1024;
+/// Default interval between `backend-health-snapshot` events
+const HEALTH_STREAM_DEFAULT_INTERVAL_MS: u64 = 1000;
+/// Default interval between `metrics-updated` events
+const DEFAULT_METRICS_INTERVAL_MS: u64 = 5000;
+const ENV_METRICS_INTERVAL_MS: &str = "ALPROJ_METRICS_INTERVAL_MS";
+/// How long stopping the backend waits for it to exit after asking it to
+/// terminate before force-killing it
+const SHUTDOWN_GRACE_SECS: u64 = 5;
+const ENV_SHUTDOWN_GRACE_SECS: &str = "ALPROJ_SHUTDOWN_GRACE_SECS";
+/// Poll interval while waiting for a terminated backend to exit
+const SHUTDOWN_POLL_MS: u64 = 100;
+/// Attempts at spawning the backend when the OS reports a transient error
+/// (e.g. `EAGAIN` under load); the delay doubles after each failure
+const SPAWN_RETRY_ATTEMPTS: u32 = 3;
+const SPAWN_RETRY_INITIAL_DELAY_MS: u64 = 200;
+/// Time allowed on quit for the force-kill after the grace period; past
+/// it the app closes anyway rather than hang on a stuck process
+const EXIT_KILL_TIMEOUT_SECS: u64 = 3;
+/// Connect timeout used by `probe_port`
+const PORT_PROBE_TIMEOUT_MS: u64 = 300;
+/// How long a restart waits for the old backend's port to be released
+const PORT_RELEASE_TIMEOUT_SECS: u64 = 10;
+const PORT_RELEASE_POLL_MS: u64 = 250;
+/// Liveness monitoring once the backend is ready
+const LIVENESS_CHECK_INTERVAL_MS: u64 = 5000;
+const LIVENESS_FAILURE_THRESHOLD: u32 = 2;
+/// Liveness latencies kept for the trend (one minute of checks)
+const LATENCY_WINDOW: usize = 12;
+/// First samples after ready that make up the latency baseline
+const LATENCY_BASELINE_SAMPLES: usize = 3;
+/// Baselines below this are raised to it, so a 1 ms backend isn't
+/// "degraded" at 3 ms
+const LATENCY_BASELINE_FLOOR_MS: f64 = 20.0;
+/// `backend-degraded` fires when the average latency exceeds this many
+/// times the baseline
+const DEFAULT_LATENCY_DEGRADED_FACTOR: u64 = 3;
+const ENV_LATENCY_DEGRADED_FACTOR: &str = "ALPROJ_LATENCY_DEGRADED_FACTOR";
+/// Reconnection backoff after the backend is lost
+const DEFAULT_RECONNECT_INITIAL_MS: u64 = 500;
+const DEFAULT_RECONNECT_MAX_MS: u64 = 10_000;
+const DEFAULT_RECONNECT_GIVE_UP_SECS: u64 = 60;
+/// Countdown before a crashed backend is restarted; `cancel_pending_restart`
+/// stops it
+const DEFAULT_RESTART_DELAY_MS: u64 = 3000;
+/// Automatic restarts allowed in a row before giving up with `backend-error`
+const DEFAULT_MAX_AUTO_RESTARTS: u32 = 3;
+/// Healthy uptime after which a backend counts as stable again and the
+/// automatic restart allowance is refilled
+const AUTO_RESTART_RESET_SECS: u64 = 120;
+/// Backend output goes to one file per stream
+const BACKEND_STDOUT_LOG_FILE_NAME: &str = "backend-stdout.log";
+const BACKEND_STDERR_LOG_FILE_NAME: &str = "backend-stderr.log";
+/// Size above which a backend log is rotated to `<name>.1` before a start
+const DEFAULT_LOG_MAX_BYTES: u64 = 10 * 1024 * 1024;
+const ENV_LOG_MAX_BYTES: &str = "ALPROJ_LOG_MAX_BYTES";
+/// Rotated logs kept (`<name>.1` is the newest)
+const DEFAULT_LOG_KEEP: u32 = 3;
+const ENV_LOG_KEEP: &str = "ALPROJ_LOG_KEEP";
+/// User settings persisted in the app config dir
+const SETTINGS_FILE_NAME: &str = "backend-settings.json";
+/// Hand-edited backend settings in the app config dir; `ALPROJ_*` env vars
+/// override its values
+const CONFIG_FILE_NAME: &str = "alproj.toml";
+/// Service name of the backend the existing commands manage; other names
+/// come from the `[services]` tables of `alproj.toml`
+const MAIN_SERVICE: &str = "main";
+/// Absolute path of the Python backend used in dev mode, instead of the
+/// `backend` directory next to `src-tauri`
+const ENV_BACKEND_DIR: &str = "ALPROJ_BACKEND_DIR";
+/// How the dev-mode backend is started: `auto`, `venv`, `uv` or `python`
+const ENV_LAUNCHER: &str = "ALPROJ_LAUNCHER";
+/// Comma-separated launchers `auto` tries in turn, e.g. `uv` to never fall
+/// back to another Python (default `venv,uv,python`)
+const ENV_LAUNCHER_ORDER: &str = "ALPROJ_LAUNCHER_ORDER";
+const DEFAULT_LAUNCHER_ORDER: &[Launcher] = &[Launcher::Venv, Launcher::Uv, Launcher::Python];
+/// System interpreters tried by the `python` launcher, in order
+#[cfg(windows)]
+const SYSTEM_PYTHON_CANDIDATES: &[&str] = &["python", "python3"];
+#[cfg(not(windows))]
+const SYSTEM_PYTHON_CANDIDATES: &[&str] = &["python3", "python"];
+/// Maximum number of lifecycle events kept for `get_events_since`
+const EVENT_JOURNAL_CAPACITY: usize = 256;
+/// Optional config file passed through to the backend
+const ENV_BACKEND_CONFIG: &str = "ALPROJ_BACKEND_CONFIG";
+/// `KEY=VALUE` file of extra environment variables for the backend
+const ENV_BACKEND_ENV_FILE: &str = "ALPROJ_BACKEND_ENV_FILE";
+/// Log level the backend reads; set in our own environment it is inherited
+/// and wins over the level saved in the settings UI
+const ENV_BACKEND_LOG_LEVEL: &str = "ALPROJ_LOG_LEVEL";
+/// Variables the GUI sets for the backend itself (`PYTHONUNBUFFERED`,
+/// `ALPROJ_LOG_LEVEL`) or that finding `uv`/Python depends on (`PATH`).
+/// Custom environment entries with these names are ignored.
+const RESERVED_BACKEND_ENV: &[&str] = &["PATH", "PYTHONUNBUFFERED", "ALPROJ_LOG_LEVEL"];
+/// Flag used to pass the config file (defaults to `--config`)
+const ENV_BACKEND_CONFIG_FLAG: &str = "ALPROJ_BACKEND_CONFIG_FLAG";
+const DEFAULT_BACKEND_CONFIG_FLAG: &str = "--config";
+/// Identity marker required in the health response before accepting readiness,
+/// either `key=value` (top-level JSON field) or `header:Name=value`
+const ENV_BACKEND_IDENTITY: &str = "ALPROJ_BACKEND_IDENTITY";
+const ENV_RECONNECT_INITIAL_MS: &str = "ALPROJ_RECONNECT_INITIAL_MS";
+const ENV_RECONNECT_MAX_MS: &str = "ALPROJ_RECONNECT_MAX_MS";
+const ENV_RECONNECT_GIVE_UP_SECS: &str = "ALPROJ_RECONNECT_GIVE_UP_SECS";
+const ENV_RESTART_DELAY_MS: &str = "ALPROJ_RESTART_DELAY_MS";
+const ENV_MAX_AUTO_RESTARTS: &str = "ALPROJ_MAX_AUTO_RESTARTS";
+/// Comma-separated endpoint paths exercised by `run_backend_selftest`
+const ENV_SELFTEST_ENDPOINTS: &str = "ALPROJ_SELFTEST_ENDPOINTS";
+/// Run the dev-mode backend with `uvicorn --reload`
+const ENV_UVICORN_RELOAD: &str = "ALPROJ_UVICORN_RELOAD";
+/// Number of uvicorn worker processes (default 1)
+const ENV_UVICORN_WORKERS: &str = "ALPROJ_UVICORN_WORKERS";
+/// uvicorn `module:attribute` target used in dev mode
+const ENV_UVICORN_APP: &str = "ALPROJ_UVICORN_APP";
+const DEFAULT_UVICORN_APP: &str = "app.main:app";
+/// HTTP method of the health check, `GET` (default) or `HEAD`
+const ENV_HEALTH_METHOD: &str = "ALPROJ_HEALTH_METHOD";
+/// Accepted health status codes, e.g. `200,204` or `2xx` (default any 2xx)
+const ENV_HEALTH_STATUS: &str = "ALPROJ_HEALTH_STATUS";
+/// Health endpoint path, e.g. `/healthz` (defaults to `/api/health`)
+const ENV_HEALTH_PATH: &str = "ALPROJ_HEALTH_PATH";
+/// `https` to reach the backend through a local TLS terminator (default `http`)
+const ENV_BACKEND_SCHEME: &str = "ALPROJ_BACKEND_SCHEME";
+/// Host name the backend is reached at (defaults to the bind address)
+const ENV_BACKEND_HOST: &str = "ALPROJ_BACKEND_HOST";
+/// Port the backend is reached at when it differs from the one it binds,
+/// e.g. a TLS terminator listening on 8443 (defaults to the bind port)
+const ENV_BACKEND_URL_PORT: &str = "ALPROJ_BACKEND_URL_PORT";
+/// Accept self-signed or otherwise invalid TLS certificates from the backend
+const ENV_ACCEPT_INVALID_CERTS: &str = "ALPROJ_ACCEPT_INVALID_CERTS";
+/// Memory ceiling in bytes for the backend and its descendants (unset: none)
+const ENV_MEMORY_LIMIT_BYTES: &str = "ALPROJ_MEMORY_LIMIT_BYTES";
+/// Pause between the OOM kill and measuring what is left of the tree
+const OOM_KILL_SETTLE_MS: u64 = 500;
+/// Preferred backend port (defaults to 8765)
+const ENV_BACKEND_PORT: &str = "ALPROJ_BACKEND_PORT";
+/// Maximum number of concurrent `backend_request` calls
+const ENV_PROXY_CONCURRENCY: &str = "ALPROJ_PROXY_CONCURRENCY";
+const DEFAULT_PROXY_CONCURRENCY: usize = 16;
+/// Show the backend console window on Windows instead of `CREATE_NO_WINDOW`
+const ENV_SHOW_BACKEND_CONSOLE: &str = "ALPROJ_SHOW_BACKEND_CONSOLE";
+/// Launch the backend detached so it outlives the GUI, and reattach to it on
+/// the next start instead of spawning a new one
+const ENV_BACKEND_DETACHED: &str = "ALPROJ_BACKEND_DETACHED";
+/// Unix domain socket path for the backend to listen on instead of TCP
+const ENV_BACKEND_UDS: &str = "ALPROJ_BACKEND_UDS";
+/// Use a backend started outside the GUI (e.g. `uvicorn --reload` in a
+/// terminal) on the configured port instead of spawning one
+const ENV_ATTACH: &str = "ALPROJ_ATTACH";
+/// Kill leftover backends of a crashed GUI that still hold a backend port
+const ENV_REAP_ORPHANS: &str = "ALPROJ_REAP_ORPHANS";
+/// Set `PYTHONUNBUFFERED=1` for the backend (on unless set to false)
+const ENV_PYTHON_UNBUFFERED: &str = "ALPROJ_PYTHON_UNBUFFERED";
+/// Check that uv runs (`uv --version`) before using it (on unless set to false)
+const ENV_VERIFY_UV: &str = "ALPROJ_VERIFY_UV";
+/// Check the sidecar binary against its bundled checksum before launching
+/// it (on unless set to false, e.g. for a locally rebuilt sidecar)
+const ENV_VERIFY_SIDECAR_CHECKSUM: &str = "ALPROJ_VERIFY_SIDECAR_CHECKSUM";
+/// How long `uv --version` may take before the candidate is rejected
+const UV_VERSION_TIMEOUT_MS: u64 = 3000;
+/// Allow `dump_state` in release builds
+const ENV_DIAGNOSTICS: &str = "ALPROJ_DIAGNOSTICS";
+/// How long `dump_state` waits for each lock before reporting it busy
+const DUMP_LOCK_TIMEOUT_MS: u64 = 200;
+/// Serve canned responses from a built-in mock instead of the Python backend
+const ENV_MOCK_BACKEND: &str = "ALPROJ_MOCK_BACKEND";
+/// Version the mock backend reports
+const MOCK_BACKEND_VERSION: &str = "0.0.0-mock";
+/// Record of the detached backend (PID and port) in the app data dir
+const DETACHED_RECORD_FILE_NAME: &str = "backend-detached.json";
 #[cfg(windows)]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
+#[cfg(windows)]
+const DETACHED_PROCESS: u32 = 0x00000008;
+#[cfg(windows)]
+const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
 
 /// Enum to hold different types of process handles
 pub enum ProcessHandle {
@@ -33,6 +286,13 @@ pub enum ProcessHandle {
     TauriChild(CommandChild),
     /// Standard process (development)
     StdChild(Child),
+    /// Built-in mock server (`ALPROJ_MOCK_BACKEND`)
+    Mock(MockBackend),
+    /// Backend we know the PID of but did not spawn, e.g. a detached one
+    /// reattached from a previous GUI run. There is no `Child` to wait on,
+    /// so it is killed by PID. It is never stopped on window close or quit;
+    /// only `stop_backend` and restarts kill it.
+    Attached { pid: u32 },
 }
 
 impl ProcessHandle {
@@ -41,10 +301,115 @@ impl ProcessHandle {
         match self {
             ProcessHandle::TauriChild(child) => Some(child.pid()),
             ProcessHandle::StdChild(ref child) => Some(child.id()),
+            ProcessHandle::Mock(_) => None,
+            ProcessHandle::Attached { pid } => Some(*pid),
+        }
+    }
+
+    /// Whether this GUI spawned the process (as opposed to attaching to it)
+    fn is_spawned(&self) -> bool {
+        !matches!(self, ProcessHandle::Attached { .. })
+    }
+
+    /// Variant name, for diagnostics
+    fn kind(&self) -> &'static str {
+        match self {
+            ProcessHandle::TauriChild(_) => "tauri_child",
+            ProcessHandle::StdChild(_) => "std_child",
+            ProcessHandle::Mock(_) => "mock",
+            ProcessHandle::Attached { .. } => "attached",
+        }
+    }
+
+    /// Ask the process to exit on its own: SIGTERM on Unix, `taskkill`
+    /// without `/F` on Windows. Returns whether the request was delivered.
+    fn request_terminate(&self) -> bool {
+        let Some(pid) = self.pid() else {
+            return false;
+        };
+        #[cfg(windows)]
+        {
+            let mut command = Command::new("taskkill");
+            command
+                .args(["/PID", &pid.to_string(), "/T"])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .creation_flags(CREATE_NO_WINDOW);
+            command.status().map(|s| s.success()).unwrap_or(false)
+        }
+        #[cfg(not(windows))]
+        {
+            let mut sys = System::new();
+            let sys_pid = Pid::from_u32(pid);
+            sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[sys_pid]), true);
+            sys.process(sys_pid)
+                .and_then(|process| process.kill_with(sysinfo::Signal::Term))
+                .unwrap_or(false)
+        }
+    }
+
+    /// Whether the process has exited (reaping it if we spawned it)
+    fn has_exited(&mut self) -> bool {
+        match self {
+            ProcessHandle::StdChild(child) => matches!(child.try_wait(), Ok(Some(_))),
+            ProcessHandle::TauriChild(child) => !process_exists(child.pid()),
+            ProcessHandle::Attached { pid } => !process_exists(*pid),
+            ProcessHandle::Mock(_) => false,
+        }
+    }
+
+    /// Ask the process to terminate and wait up to `grace` for it to exit,
+    /// so uvicorn can flush logs and close connections; force-kill the
+    /// process tree with `kill` if it is still running after that
+    pub async fn shutdown(mut self, grace: Duration) -> Result<(), String> {
+        // uvicorn workers are reparented once their supervisor exits, so
+        // note them (with start times, to recognize reused PIDs) now to reap
+        // any that outlive a graceful exit
+        let children = match self.pid() {
+            Some(pid) if !grace.is_zero() => tauri::async_runtime::spawn_blocking(move || {
+                let mut sys = System::new();
+                sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+                collect_descendants(&sys, pid)
+                    .into_iter()
+                    .filter_map(|pid| {
+                        let process = sys.process(Pid::from_u32(pid))?;
+                        Some((pid, process.start_time()))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .await
+            .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        if !grace.is_zero() && self.request_terminate() {
+            info!("Asked backend PID {:?} to terminate", self.pid());
+            let start = std::time::Instant::now();
+            while start.elapsed() < grace {
+                if self.has_exited() {
+                    info!("Backend exited gracefully");
+                    if !children.is_empty() {
+                        tauri::async_runtime::spawn_blocking(move || {
+                            kill_leftover_processes(&children)
+                        })
+                        .await
+                        .map_err(|e| format!("Kill task failed: {}", e))?;
+                    }
+                    return Ok(());
+                }
+                sleep(Duration::from_millis(SHUTDOWN_POLL_MS)).await;
+            }
+            warn!(
+                "Backend did not exit within {} ms; killing it",
+                grace.as_millis()
+            );
         }
+        // Killing the tree refreshes the whole process table
+        tauri::async_runtime::spawn_blocking(move || self.kill())
+            .await
+            .map_err(|e| format!("Kill task failed: {}", e))?
     }
 
-    /// Kill the process and all its children, consuming self
+    /// Kill the process and all its children immediately, consuming self
     pub fn kill(self) -> Result<(), String> {
         // First, kill all child processes
         if let Some(pid) = self.pid() {
@@ -56,11 +421,198 @@ impl ProcessHandle {
         match self {
             ProcessHandle::TauriChild(child) => child.kill().map_err(|e| e.to_string()),
             ProcessHandle::StdChild(mut child) => child.kill().map_err(|e| e.to_string()),
+            ProcessHandle::Mock(mock) => {
+                mock.stop();
+                Ok(())
+            }
+            ProcessHandle::Attached { pid } => {
+                let mut sys = System::new();
+                let sys_pid = Pid::from_u32(pid);
+                sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[sys_pid]), true);
+                match sys.process(sys_pid) {
+                    Some(process) if !process.kill() => {
+                        Err(format!("Failed to kill attached backend PID {}", pid))
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Stand-in for the Python backend, serving canned JSON on a background
+/// thread so frontend work and e2e tests don't need Python or its deps.
+///
+/// Health answers as our backend (`"app": "alproj"`), so readiness, the
+/// monitor and `check_backend_health` behave as with the real one. Only a
+/// few stub endpoints exist; everything else is a FastAPI-style 404.
+pub struct MockBackend {
+    /// Port actually bound, which differs from the requested one for port 0
+    port: u16,
+    stop: Arc<AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl MockBackend {
+    fn start(host: &str, port: u16) -> Result<Self, String> {
+        let listener = std::net::TcpListener::bind((host, port))
+            .map_err(|e| format!("Failed to bind mock backend on {}:{}: {}", host, port, e))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| format!("Failed to read mock backend address: {}", e))?
+            .port();
+        // Non-blocking so the accept loop notices `stop`
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| format!("Failed to configure mock backend listener: {}", e))?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread = std::thread::Builder::new()
+            .name("mock-backend".to_string())
+            .spawn(move || {
+                while !thread_stop.load(Ordering::SeqCst) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            if let Err(e) = serve_mock_connection(stream) {
+                                warn!("Mock backend request failed: {}", e);
+                            }
+                        }
+                        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                            std::thread::sleep(Duration::from_millis(20));
+                        }
+                        Err(e) => {
+                            warn!("Mock backend accept failed: {}", e);
+                            std::thread::sleep(Duration::from_millis(100));
+                        }
+                    }
+                }
+            })
+            .map_err(|e| format!("Failed to start mock backend thread: {}", e))?;
+        Ok(Self {
+            port,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Stop accepting and wait for the server thread (and its listener) to end
+    fn stop(mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Canned status and JSON body for a mock backend request
+fn mock_response(method: &str, path: &str) -> (u16, serde_json::Value) {
+    match (method, path) {
+        ("GET" | "HEAD", HEALTH_CHECK_PATH) => (
+            200,
+            serde_json::json!({
+                "status": "ok",
+                "app": "alproj",
+                "mock": true,
+                "active_requests": 0,
+            }),
+        ),
+        ("GET", VERSION_PATH) => (
+            200,
+            serde_json::json!({ "version": MOCK_BACKEND_VERSION, "commit": "mock" }),
+        ),
+        ("GET", "/") => (
+            200,
+            serde_json::json!({
+                "message": "alproj-gui mock backend",
+                "version": MOCK_BACKEND_VERSION,
+            }),
+        ),
+        ("GET", "/openapi.json") => (
+            200,
+            serde_json::json!({
+                "openapi": "3.1.0",
+                "info": { "title": "alproj-gui mock backend", "version": MOCK_BACKEND_VERSION },
+                "paths": {},
+            }),
+        ),
+        ("POST", DRAIN_PATH) => (200, serde_json::json!({ "status": "draining" })),
+        _ => (404, serde_json::json!({ "detail": "Not Found" })),
+    }
+}
+
+/// Answer one HTTP/1.1 request and close the connection
+fn serve_mock_connection(mut stream: std::net::TcpStream) -> std::io::Result<()> {
+    use std::io::Write;
+
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 4096];
+    let header_end = loop {
+        if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if request.len() > 16 * 1024 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "request headers too large",
+            ));
+        }
+        let read = stream.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(());
+        }
+        request.extend_from_slice(&buffer[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&request[..header_end]).to_string();
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let target = request_line.next().unwrap_or("/");
+    let path = target.split('?').next().unwrap_or(target);
+
+    // Drain the body so closing the socket doesn't reset the connection
+    let content_length = head
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                value.trim().parse::<usize>().ok()
+            } else {
+                None
+            }
+        })
+        .unwrap_or(0);
+    let mut remaining = content_length.saturating_sub(request.len() - header_end);
+    while remaining > 0 {
+        let read = stream.read(&mut buffer[..remaining.min(4096)])?;
+        if read == 0 {
+            break;
         }
+        remaining -= read;
+    }
+
+    let (status, body) = mock_response(method, path);
+    let body = body.to_string();
+    let reason = if status == 200 { "OK" } else { "Not Found" };
+    let mut response = format!(
+        "HTTP/1.1 {} {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+        status,
+        reason,
+        body.len()
+    );
+    if method != "HEAD" {
+        response.push_str(&body);
     }
+    stream.write_all(response.as_bytes())?;
+    stream.flush()
 }
 
-/// Kill a process and all its descendant processes
+/// Kill a process and all its descendant processes.
+///
+/// On Windows `taskkill /T /F` runs first, while every process in the tree
+/// still has its parent, and takes the root down as well; the processes
+/// found in the snapshot are then swept as a fallback for anything it missed.
 fn kill_process_tree(root_pid: u32) {
     let mut sys = System::new();
     sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
@@ -68,7 +620,31 @@ fn kill_process_tree(root_pid: u32) {
     // Collect all descendant PIDs first
     let descendants = collect_descendants(&sys, root_pid);
 
+    #[cfg(windows)]
+    {
+        let status = Command::new("taskkill")
+            .args(["/PID", &root_pid.to_string(), "/T", "/F"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .creation_flags(CREATE_NO_WINDOW)
+            .status();
+        match status {
+            Ok(status) => info!("taskkill /T /F for PID {} exited with {}", root_pid, status),
+            Err(e) => warn!("Failed to run taskkill for PID {}: {}", root_pid, e),
+        }
+        // Start times guard against PIDs reused since the snapshot
+        let tree: Vec<(u32, u64)> = std::iter::once(root_pid)
+            .chain(descendants)
+            .filter_map(|pid| {
+                sys.process(Pid::from_u32(pid))
+                    .map(|process| (pid, process.start_time()))
+            })
+            .collect();
+        kill_leftover_processes(&tree);
+    }
+
     // Kill descendants in reverse order (children before parents)
+    #[cfg(not(windows))]
     for pid in descendants.iter().rev() {
         if let Some(process) = sys.process(Pid::from_u32(*pid)) {
             info!(
@@ -81,758 +657,9083 @@ fn kill_process_tree(root_pid: u32) {
     }
 }
 
-/// Recursively collect all descendant process IDs
-fn collect_descendants(sys: &System, parent_pid: u32) -> Vec<u32> {
-    let mut descendants = Vec::new();
-    let parent_pid_obj = Pid::from_u32(parent_pid);
-
-    for (pid, process) in sys.processes() {
-        if let Some(ppid) = process.parent() {
-            if ppid == parent_pid_obj {
-                let child_pid = pid.as_u32();
-                descendants.push(child_pid);
-                // Recursively collect children of this child
-                descendants.extend(collect_descendants(sys, child_pid));
-            }
+/// Kill any of `processes` (PID and start time) still running, e.g. workers
+/// left behind by a backend that exited on its own. A PID whose process
+/// started at another time was reused by an unrelated process and is left
+/// alone.
+fn kill_leftover_processes(processes: &[(u32, u64)]) {
+    let sys_pids: Vec<Pid> = processes
+        .iter()
+        .map(|(pid, _)| Pid::from_u32(*pid))
+        .collect();
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&sys_pids), true);
+    for (pid, start_time) in processes.iter().rev() {
+        let pid = Pid::from_u32(*pid);
+        if let Some(process) = sys
+            .process(pid)
+            .filter(|process| process.start_time() == *start_time)
+        {
+            warn!(
+                "Killing leftover backend process {} ({})",
+                pid,
+                process.name().to_string_lossy()
+            );
+            process.kill();
         }
     }
-
-    descendants
 }
 
-/// Application state for managing the Python backend sidecar
-pub struct AppState {
-    /// Sidecar process handle
-    pub sidecar: Mutex<Option<ProcessHandle>>,
-    /// Backend ready flag
-    pub backend_ready: Mutex<bool>,
-    /// Sidecar log file path (production mode)
-    pub backend_log_path: Mutex<Option<PathBuf>>,
+/// Recursively collect all descendant process IDs, parents before children
+fn collect_descendants(sys: &System, parent_pid: u32) -> Vec<u32> {
+    let parents: Vec<(u32, u32)> = sys
+        .processes()
+        .iter()
+        .filter_map(|(pid, process)| Some((pid.as_u32(), process.parent()?.as_u32())))
+        .collect();
+    let mut visited = HashSet::from([parent_pid]);
+    let mut descendants = Vec::new();
+    collect_descendants_from(&parents, parent_pid, &mut visited, &mut descendants);
+    descendants
 }
 
-impl Default for AppState {
-    fn default() -> Self {
-        Self {
-            sidecar: Mutex::new(None),
-            backend_ready: Mutex::new(false),
-            backend_log_path: Mutex::new(None),
+/// Walk `(pid, parent pid)` pairs below `parent_pid`. PIDs already in
+/// `visited` are skipped, so a cycle (PID reuse during a refresh) can't
+/// recurse forever or list a process twice.
+fn collect_descendants_from(
+    parents: &[(u32, u32)],
+    parent_pid: u32,
+    visited: &mut HashSet<u32>,
+    descendants: &mut Vec<u32>,
+) {
+    for &(pid, ppid) in parents {
+        if ppid == parent_pid && visited.insert(pid) {
+            descendants.push(pid);
+            collect_descendants_from(parents, pid, visited, descendants);
         }
     }
 }
 
-#[derive(serde::Serialize)]
-struct BackendLogChunk {
-    next_offset: usize,
-    text: String,
+/// Read an environment variable, treating empty values as unset
+fn env_var_non_empty(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
 }
 
-fn resolve_backend_log_path(app: &tauri::AppHandle) -> PathBuf {
-    if let Ok(log_dir) = app.path().app_log_dir() {
-        return log_dir.join(BACKEND_LOG_FILE_NAME);
-    }
-    if let Ok(data_dir) = app.path().app_data_dir() {
-        return data_dir.join("logs").join(BACKEND_LOG_FILE_NAME);
+/// Parse a positive integer environment variable, warning on invalid values
+fn env_positive_u64(name: &str) -> Option<u64> {
+    let raw = env_var_non_empty(name)?;
+    match raw.parse::<u64>() {
+        Ok(value) if value > 0 => Some(value),
+        _ => {
+            warn!(
+                "Ignoring invalid {}={:?} (expected a positive integer)",
+                name, raw
+            );
+            None
+        }
     }
-    std::env::temp_dir()
-        .join("alproj-gui")
-        .join(BACKEND_LOG_FILE_NAME)
 }
 
-fn format_log_tail(log_path: &Path, max_lines: usize) -> String {
-    let bytes = match fs::read(log_path) {
-        Ok(bytes) => bytes,
-        Err(e) => {
-            return format!("Backend log read failed: {} ({})", e, log_path.display());
+/// Parse a boolean environment variable (`1/true/yes/on`, `0/false/no/off`)
+fn env_flag(name: &str) -> Option<bool> {
+    let raw = env_var_non_empty(name)?;
+    match raw.to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => {
+            warn!(
+                "Ignoring invalid {}={:?} (expected true or false)",
+                name, raw
+            );
+            None
         }
-    };
-
-    let text = String::from_utf8_lossy(&bytes);
-    let lines: Vec<&str> = text.lines().collect();
-    let start = lines.len().saturating_sub(max_lines);
-    let mut tail = lines[start..].join("\n");
-
-    const MAX_CHARS: usize = 4000;
-    if tail.chars().count() > MAX_CHARS {
-        tail = tail
-            .chars()
-            .rev()
-            .take(MAX_CHARS)
-            .collect::<String>()
-            .chars()
-            .rev()
-            .collect::<String>();
     }
-
-    format!(
-        "Backend log: {}\n--- log tail ---\n{}\n----------------",
-        log_path.display(),
-        tail
-    )
-}
-
-async fn read_backend_log_tail(state: &Arc<AppState>, max_lines: usize) -> Option<String> {
-    let log_path = state.backend_log_path.lock().await.clone();
-    log_path.map(|path| format_log_tail(&path, max_lines))
 }
 
-async fn check_sidecar_exited(state: &Arc<AppState>) -> Option<String> {
-    let exit = {
-        let mut sidecar = state.sidecar.lock().await;
-        match sidecar.as_mut() {
-            Some(ProcessHandle::StdChild(child)) => match child.try_wait() {
-                Ok(Some(status)) => Some(status),
-                Ok(None) => None,
-                Err(e) => {
-                    return Some(format!("Failed to query backend process status: {}", e));
-                }
-            },
-            _ => None,
-        }
+/// Parse one status code pattern: `204`, `2xx` or `200-299`
+fn parse_status_range(part: &str) -> Option<RangeInclusive<u16>> {
+    let range = if let Some(class) = part.strip_suffix("xx") {
+        let class = class
+            .parse::<u16>()
+            .ok()
+            .filter(|class| (1..=5).contains(class))?;
+        class * 100..=class * 100 + 99
+    } else if let Some((low, high)) = part.split_once('-') {
+        low.trim().parse().ok()?..=high.trim().parse().ok()?
+    } else {
+        let code = part.parse().ok()?;
+        code..=code
     };
-
-    if let Some(status) = exit {
-        let code_text = match status.code() {
-            Some(code) => format!("exit code {}", code),
-            None => "terminated by signal".to_string(),
-        };
-        if let Some(log_tail) = read_backend_log_tail(state, 80).await {
-            return Some(format!(
-                "Backend process exited before ready ({})\n{}",
-                code_text, log_tail
-            ));
-        }
-        return Some(format!(
-            "Backend process exited before ready ({})",
-            code_text
-        ));
-    }
-
-    None
+    let valid = |code: &u16| (100..=599).contains(code);
+    (valid(range.start()) && valid(range.end()) && !range.is_empty()).then_some(range)
 }
 
-/// Check if we're running in development mode
-fn is_dev_mode() -> bool {
-    cfg!(debug_assertions)
+/// Parse a comma-separated list of status code patterns
+fn parse_status_codes(spec: &str) -> Option<Vec<RangeInclusive<u16>>> {
+    let ranges = spec
+        .split(',')
+        .map(|part| part.trim().to_ascii_lowercase())
+        .filter(|part| !part.is_empty())
+        .map(|part| parse_status_range(&part))
+        .collect::<Option<Vec<_>>>()?;
+    (!ranges.is_empty()).then_some(ranges)
 }
 
-/// Find uv executable in common installation locations
-/// Tauri doesn't inherit the shell PATH, so we need to check common paths
-fn find_uv_path() -> Option<String> {
-    let home = std::env::var("HOME").ok()?;
-
-    // Common uv installation paths
-    let candidates = [
-        format!("{}/.local/bin/uv", home),
-        format!("{}/.cargo/bin/uv", home),
-        "/usr/local/bin/uv".to_string(),
-        "/opt/homebrew/bin/uv".to_string(),
-        // Also check if uv is in PATH (in case it works)
-        "uv".to_string(),
-    ];
+/// Marker that identifies our backend in a health response
+#[derive(Clone, Debug, PartialEq)]
+pub enum IdentityMarker {
+    /// Top-level JSON field of the health body, e.g. `{"app":"alproj"}`
+    BodyField { key: String, value: String },
+    /// Response header with an exact value
+    Header { name: String, value: String },
+}
 
-    for path in candidates {
-        if path == "uv" {
-            // For plain "uv", just return it and let the shell try
-            continue;
+impl IdentityMarker {
+    /// Parse `key=value` or `header:Name=value`
+    fn parse(spec: &str) -> Option<Self> {
+        let (target, value) = spec.split_once('=')?;
+        let value = value.trim().to_string();
+        if let Some(name) = target.strip_prefix("header:") {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                return None;
+            }
+            return Some(IdentityMarker::Header { name, value });
         }
-        if std::path::Path::new(&path).exists() {
-            return Some(path);
+        let key = target.trim().to_string();
+        if key.is_empty() {
+            return None;
         }
+        Some(IdentityMarker::BodyField { key, value })
     }
 
-    // Fallback to plain "uv" if no absolute path found
-    Some("uv".to_string())
+    fn matches(&self, headers: &reqwest::header::HeaderMap, body: &[u8]) -> bool {
+        match self {
+            IdentityMarker::Header { name, value } => headers
+                .get(name.as_str())
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v == value)
+                .unwrap_or(false),
+            IdentityMarker::BodyField { key, value } => {
+                serde_json::from_slice::<serde_json::Value>(body)
+                    .ok()
+                    .and_then(|json| match json.get(key) {
+                        Some(serde_json::Value::String(s)) => Some(s == value),
+                        Some(other) => Some(&other.to_string() == value),
+                        None => None,
+                    })
+                    .unwrap_or(false)
+            }
+        }
+    }
 }
 
-fn get_dev_backend_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
-    let src_tauri_dir = app
-        .path()
-        .resource_dir()
-        .map_err(|e| format!("Failed to get resource dir: {}", e))?
-        .parent()
-        .ok_or("Failed to get parent dir")?
-        .parent()
-        .ok_or("Failed to get src-tauri dir")?
-        .to_path_buf();
-
-    src_tauri_dir
-        .parent()
-        .ok_or_else(|| "Failed to get project root".to_string())
-        .map(|p| p.join("backend"))
+impl std::fmt::Display for IdentityMarker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdentityMarker::BodyField { key, value } => write!(f, "{}={}", key, value),
+            IdentityMarker::Header { name, value } => write!(f, "header:{}={}", name, value),
+        }
+    }
 }
 
-fn find_dev_python(backend_dir: &Path) -> Option<PathBuf> {
-    #[cfg(windows)]
-    let candidates = [
-        backend_dir.join(".venv").join("Scripts").join("python.exe"),
-        backend_dir
-            .join(".venv")
-            .join("Scripts")
-            .join("python3.exe"),
-    ];
-    #[cfg(not(windows))]
-    let candidates = [
-        backend_dir.join(".venv").join("bin").join("python"),
-        backend_dir.join(".venv").join("bin").join("python3"),
-    ];
-
-    candidates.into_iter().find(|p| p.exists())
+/// Backend launch settings resolved at startup
+#[derive(Clone, Debug)]
+pub struct BackendConfig {
+    /// Preferred port (`ALPROJ_BACKEND_PORT`); the next free one within
+    /// `PORT_SEARCH_SPAN` is used when it is taken
+    pub port: u16,
+    /// Config file passed to the backend (`ALPROJ_BACKEND_CONFIG`)
+    pub config_file: Option<PathBuf>,
+    /// Flag name used to pass `config_file` (`ALPROJ_BACKEND_CONFIG_FLAG`)
+    pub config_flag: String,
+    /// Required health-response marker (`ALPROJ_BACKEND_IDENTITY`), off by default
+    pub identity_marker: Option<IdentityMarker>,
+    /// First reconnect delay after the backend is lost (`ALPROJ_RECONNECT_INITIAL_MS`)
+    pub reconnect_initial_ms: u64,
+    /// Upper bound for the reconnect delay (`ALPROJ_RECONNECT_MAX_MS`)
+    pub reconnect_max_ms: u64,
+    /// How long to keep reconnecting before declaring a crash (`ALPROJ_RECONNECT_GIVE_UP_SECS`)
+    pub reconnect_give_up_secs: u64,
+    /// Delay before a crashed backend is restarted (`ALPROJ_RESTART_DELAY_MS`)
+    pub restart_delay_ms: u64,
+    /// Grace period between asking the backend to terminate and killing it
+    /// (`ALPROJ_SHUTDOWN_GRACE_SECS`)
+    pub shutdown_grace_secs: u64,
+    /// Crash restarts in a row before giving up (`ALPROJ_MAX_AUTO_RESTARTS`);
+    /// `0` turns auto-restart off
+    pub max_auto_restarts: u32,
+    /// Multiple of the baseline health latency that counts as degraded
+    /// (`ALPROJ_LATENCY_DEGRADED_FACTOR`)
+    pub latency_degraded_factor: u64,
+    /// Default interval of `start_metrics_stream` (`ALPROJ_METRICS_INTERVAL_MS`)
+    pub metrics_interval_ms: u64,
+    /// Show the backend console window on Windows (`ALPROJ_SHOW_BACKEND_CONSOLE`).
+    /// Output is still written to the backend log file.
+    pub show_console: bool,
+    /// Endpoint paths checked by `run_backend_selftest` (`ALPROJ_SELFTEST_ENDPOINTS`)
+    pub selftest_endpoints: Vec<String>,
+    /// How long `wait_for_backend` keeps polling the health check
+    /// (`ALPROJ_HEALTH_TIMEOUT_SECS`, or `set_health_timeout`)
+    pub health_timeout_secs: u64,
+    /// Pass `--reload` to uvicorn in dev mode (`ALPROJ_UVICORN_RELOAD`).
+    ///
+    /// The process we spawn is then uvicorn's reloader, which replaces its
+    /// worker child on every code change. The reloader is what we track for
+    /// liveness: health failures while it is alive are treated as a reload in
+    /// progress (up to `RELOAD_GRACE_SECS`), and only its exit counts as a
+    /// crash. Stopping the backend kills the reloader and its current worker.
+    pub uvicorn_reload: bool,
+    /// uvicorn worker processes (`ALPROJ_UVICORN_WORKERS`, or `workers` in
+    /// `alproj.toml`). With more than one, the process we spawn is uvicorn's
+    /// supervisor and the workers are its children; stopping the backend
+    /// kills them along with it. Ignored together with `uvicorn_reload`.
+    pub uvicorn_workers: u32,
+    /// Health check method (`ALPROJ_HEALTH_METHOD`), GET or HEAD
+    pub health_method: reqwest::Method,
+    /// Health status codes counted as healthy (`ALPROJ_HEALTH_STATUS`)
+    pub health_statuses: Vec<RangeInclusive<u16>>,
+    /// Health endpoint path (`ALPROJ_HEALTH_PATH`)
+    pub health_path: String,
+    /// `http` or `https` (`ALPROJ_BACKEND_SCHEME`).
+    ///
+    /// Together with `host` this only changes how the backend is reached;
+    /// it still binds to `BACKEND_HOST` on the chosen port.
+    pub scheme: String,
+    /// Host in backend URLs (`ALPROJ_BACKEND_HOST`)
+    pub host: String,
+    /// Port in backend URLs (`ALPROJ_BACKEND_URL_PORT`), when it is not the
+    /// port the backend binds
+    pub url_port: Option<u16>,
+    /// Skip TLS certificate checks (`ALPROJ_ACCEPT_INVALID_CERTS`), off by
+    /// default; only meant for a local terminator with a self-signed cert
+    pub accept_invalid_certs: bool,
+    /// uvicorn app target in dev mode (`ALPROJ_UVICORN_APP`), e.g. `app.main:app`
+    pub uvicorn_app: String,
+    /// Run the backend detached (`ALPROJ_BACKEND_DETACHED`), off by default.
+    ///
+    /// The backend then survives GUI crashes and normal quits, and the next
+    /// GUI start reattaches to it (after checking the recorded PID still
+    /// answers health as our backend). The tradeoff is ownership: closing
+    /// the window no longer stops the backend, so it keeps using memory and
+    /// its port until it is stopped explicitly (`stop_backend`, a restart)
+    /// or killed by the user.
+    pub detached: bool,
+    /// Don't spawn a backend, just wait for one started separately to answer
+    /// health on `port` (`ALPROJ_ATTACH`), off by default. It is never
+    /// stopped or restarted by the GUI since it isn't ours.
+    pub attach: bool,
+    /// Kill backends left over from a GUI crash that still listen on a
+    /// backend port before starting (`ALPROJ_REAP_ORPHANS`), off by default.
+    /// Only the sidecar binary or a uvicorn serving `uvicorn_app` is killed.
+    pub reap_orphans: bool,
+    /// Unix domain socket the backend listens on instead of a TCP port
+    /// (`ALPROJ_BACKEND_UDS`); unsupported on Windows.
+    ///
+    /// Only this process can reach the socket, so the webview has to go
+    /// through `backend_request` in this mode.
+    pub uds_path: Option<PathBuf>,
+    /// Serve canned responses from `MockBackend` instead of starting the
+    /// Python backend (`ALPROJ_MOCK_BACKEND`), off by default
+    pub mock: bool,
+    /// Pass `PYTHONUNBUFFERED=1` (`ALPROJ_PYTHON_UNBUFFERED`, on by default).
+    ///
+    /// Python block-buffers stdout when it isn't a TTY, so without this
+    /// startup output reaches the log file in bursts. Logging output
+    /// (ours and uvicorn's access/error loggers) goes through
+    /// `StreamHandler`, which flushes after every record either way.
+    pub python_unbuffered: bool,
+    /// Only use a uv that answers `uv --version` (`ALPROJ_VERIFY_UV`)
+    pub verify_uv: bool,
+    /// How the dev-mode backend is started (`ALPROJ_LAUNCHER`)
+    pub launcher: Launcher,
+    /// Launchers tried in turn when `launcher` is `auto`
+    /// (`ALPROJ_LAUNCHER_ORDER`): the next one is used when one is missing,
+    /// fails to spawn, or its backend exits before becoming healthy
+    pub launcher_order: Vec<Launcher>,
+    /// Refuse to launch a sidecar whose SHA-256 doesn't match the bundled
+    /// `checksum.txt` (`ALPROJ_VERIFY_SIDECAR_CHECKSUM`)
+    pub verify_sidecar_checksum: bool,
+    /// Backend log level passed as `ALPROJ_LOG_LEVEL` (set via `update_settings`)
+    pub backend_log_level: Option<String>,
+    /// Extra environment variables for the backend (set via `update_settings`)
+    pub extra_env: HashMap<String, String>,
+    /// Variables from the `[env]` table of `alproj.toml`, then
+    /// `ALPROJ_BACKEND_ENV_FILE`; `extra_env` wins on conflicts
+    pub env_file_vars: HashMap<String, String>,
+    /// Rotate the backend log once it is larger than this (`ALPROJ_LOG_MAX_BYTES`)
+    pub log_max_bytes: u64,
+    /// Rotated backend logs kept (`ALPROJ_LOG_KEEP`)
+    pub log_keep: u32,
+    /// Concurrent proxied requests allowed before callers queue (`ALPROJ_PROXY_CONCURRENCY`)
+    pub proxy_concurrency: usize,
+    /// Kill and restart the backend once it and its descendants use more
+    /// memory than this (`ALPROJ_MEMORY_LIMIT_BYTES`); off by default
+    pub memory_limit_bytes: Option<u64>,
+    /// Extra backend processes by name, from `[services.<name>]` in
+    /// `alproj.toml`. They are started, stopped and restarted along with the
+    /// main backend, and started again by `monitor_services` if they exit.
+    pub services: HashMap<String, ServiceConfig>,
 }
 
-fn is_backend_process_for_dir(process: &sysinfo::Process, backend_dir: &Path) -> bool {
-    let cmd = process
-        .cmd()
-        .iter()
-        .map(|arg| arg.to_string_lossy().to_string())
-        .collect::<Vec<_>>()
-        .join(" ");
-    if cmd.is_empty() {
-        return false;
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self {
+            port: BACKEND_PORT,
+            config_file: None,
+            config_flag: DEFAULT_BACKEND_CONFIG_FLAG.to_string(),
+            identity_marker: None,
+            reconnect_initial_ms: DEFAULT_RECONNECT_INITIAL_MS,
+            reconnect_max_ms: DEFAULT_RECONNECT_MAX_MS,
+            reconnect_give_up_secs: DEFAULT_RECONNECT_GIVE_UP_SECS,
+            restart_delay_ms: DEFAULT_RESTART_DELAY_MS,
+            shutdown_grace_secs: SHUTDOWN_GRACE_SECS,
+            max_auto_restarts: DEFAULT_MAX_AUTO_RESTARTS,
+            latency_degraded_factor: DEFAULT_LATENCY_DEGRADED_FACTOR,
+            metrics_interval_ms: DEFAULT_METRICS_INTERVAL_MS,
+            show_console: false,
+            selftest_endpoints: DEFAULT_SELFTEST_ENDPOINTS
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            health_timeout_secs: HEALTH_CHECK_TIMEOUT_SECS,
+            uvicorn_reload: false,
+            uvicorn_workers: 1,
+            uvicorn_app: DEFAULT_UVICORN_APP.to_string(),
+            health_method: reqwest::Method::GET,
+            detached: false,
+            attach: false,
+            reap_orphans: false,
+            uds_path: None,
+            mock: false,
+            python_unbuffered: true,
+            verify_uv: true,
+            launcher: Launcher::Auto,
+            launcher_order: DEFAULT_LAUNCHER_ORDER.to_vec(),
+            verify_sidecar_checksum: true,
+            backend_log_level: None,
+            extra_env: HashMap::new(),
+            env_file_vars: HashMap::new(),
+            log_max_bytes: DEFAULT_LOG_MAX_BYTES,
+            log_keep: DEFAULT_LOG_KEEP,
+            health_statuses: vec![200..=299],
+            health_path: HEALTH_CHECK_PATH.to_string(),
+            scheme: "http".to_string(),
+            host: BACKEND_HOST.to_string(),
+            url_port: None,
+            accept_invalid_certs: false,
+            proxy_concurrency: DEFAULT_PROXY_CONCURRENCY,
+            memory_limit_bytes: None,
+            services: HashMap::new(),
+        }
     }
+}
 
-    let looks_like_uvicorn =
-        cmd.contains("uvicorn") && cmd.contains("app.main:app") && cmd.contains("8765");
-    if !looks_like_uvicorn {
-        return false;
+impl BackendConfig {
+    /// Build the config from defaults overridden by `ALPROJ_*` env vars
+    pub fn from_env() -> Self {
+        Self::load(None)
     }
 
-    if let Some(cwd) = process.cwd() {
-        if cwd.starts_with(backend_dir) {
-            return true;
+    /// Build the config from defaults, overridden by `alproj.toml` in
+    /// `config_dir` (if there is one), overridden by `ALPROJ_*` env vars
+    pub fn load(config_dir: Option<&Path>) -> Self {
+        let mut config = Self::default();
+        if let Some(file) = config_dir.and_then(read_config_file) {
+            config.apply_file(file);
         }
+        config.apply_env();
+        config
     }
 
-    if let Some(exe) = process.exe() {
-        if exe.starts_with(backend_dir) {
-            return true;
+    /// Overlay the values set in `alproj.toml`
+    fn apply_file(&mut self, file: FileConfig) {
+        match file.port {
+            Some(0) => warn!("Ignoring port 0 in {}", CONFIG_FILE_NAME),
+            Some(port) => self.port = port,
+            None => {}
+        }
+        if let Some(secs) = file.health_timeout_secs {
+            self.health_timeout_secs = secs.clamp(1, MAX_HEALTH_CHECK_TIMEOUT_SECS);
+        }
+        match file.health_path {
+            Some(path) if path.starts_with('/') => self.health_path = path,
+            Some(path) => warn!(
+                "Ignoring health_path {:?} in {} (expected a path starting with /)",
+                path, CONFIG_FILE_NAME
+            ),
+            None => {}
+        }
+        if let Some(launcher) = file.launcher {
+            self.launcher = launcher;
+        }
+        match file.launcher_order {
+            Some(order) if !order.is_empty() && !order.contains(&Launcher::Auto) => {
+                self.launcher_order = order
+            }
+            Some(order) => warn!(
+                "Ignoring launcher_order {:?} in {} (expected launchers among venv, uv and python)",
+                order, CONFIG_FILE_NAME
+            ),
+            None => {}
+        }
+        match file.workers {
+            Some(0) => warn!("Ignoring workers = 0 in {}", CONFIG_FILE_NAME),
+            Some(workers) => self.uvicorn_workers = workers,
+            None => {}
+        }
+        self.env_file_vars = file.env;
+        for (name, service) in file.services {
+            match validate_service(&name, &service) {
+                Ok(()) => {
+                    self.services.insert(name, service);
+                }
+                Err(e) => warn!("Ignoring service {:?} in {}: {}", name, CONFIG_FILE_NAME, e),
+            }
         }
     }
 
-    cmd.contains(backend_dir.to_string_lossy().as_ref())
-}
-
-fn cleanup_stale_backend_processes(backend_dir: &Path) -> usize {
-    let mut sys = System::new();
-    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
-
-    let current_pid = std::process::id();
-    let stale_pids = sys
-        .processes()
-        .iter()
-        .filter_map(|(pid, process)| {
-            let pid_u32 = pid.as_u32();
-            if pid_u32 == current_pid {
-                return None;
+    /// Overlay the `ALPROJ_*` environment variables that are set
+    fn apply_env(&mut self) {
+        let config = self;
+        if let Some(port) = env_var_non_empty(ENV_BACKEND_PORT) {
+            match port.parse::<u16>() {
+                Ok(port) if port > 0 => config.port = port,
+                _ => warn!("Ignoring invalid {}={:?}", ENV_BACKEND_PORT, port),
             }
-            if is_backend_process_for_dir(process, backend_dir) {
-                return Some(pid_u32);
+        }
+        if let Some(path) = env_var_non_empty(ENV_BACKEND_CONFIG) {
+            config.config_file = Some(PathBuf::from(path));
+        }
+        if let Some(flag) = env_var_non_empty(ENV_BACKEND_CONFIG_FLAG) {
+            config.config_flag = flag;
+        }
+        if let Some(bytes) = env_positive_u64(ENV_LOG_MAX_BYTES) {
+            config.log_max_bytes = bytes;
+        }
+        if let Some(keep) = env_positive_u64(ENV_LOG_KEEP) {
+            config.log_keep = keep.min(100) as u32;
+        }
+        if let Some(path) = env_var_non_empty(ENV_BACKEND_ENV_FILE) {
+            match fs::read_to_string(&path) {
+                Ok(text) => config.env_file_vars.extend(parse_env_file(&text)),
+                Err(e) => warn!("Ignoring {}={:?}: {}", ENV_BACKEND_ENV_FILE, path, e),
             }
-            None
-        })
-        .collect::<Vec<_>>();
-
-    for pid in &stale_pids {
-        warn!("Killing stale backend process PID {}", pid);
-        kill_process_tree(*pid);
-        if let Some(process) = sys.process(Pid::from_u32(*pid)) {
-            process.kill();
+        }
+        if let Some(spec) = env_var_non_empty(ENV_BACKEND_IDENTITY) {
+            match IdentityMarker::parse(&spec) {
+                Some(marker) => config.identity_marker = Some(marker),
+                None => warn!(
+                    "Ignoring invalid {}={:?} (expected key=value or header:Name=value)",
+                    ENV_BACKEND_IDENTITY, spec
+                ),
+            }
+        }
+        if let Some(secs) = env_positive_u64(ENV_HEALTH_TIMEOUT_SECS) {
+            config.health_timeout_secs = secs.min(MAX_HEALTH_CHECK_TIMEOUT_SECS);
+        }
+        if let Some(ms) = env_positive_u64(ENV_RECONNECT_INITIAL_MS) {
+            config.reconnect_initial_ms = ms;
+        }
+        if let Some(ms) = env_positive_u64(ENV_RECONNECT_MAX_MS) {
+            config.reconnect_max_ms = ms;
+        }
+        if let Some(secs) = env_positive_u64(ENV_RECONNECT_GIVE_UP_SECS) {
+            config.reconnect_give_up_secs = secs;
+        }
+        if let Some(ms) = env_positive_u64(ENV_RESTART_DELAY_MS) {
+            config.restart_delay_ms = ms;
+        }
+        if let Some(secs) = env_positive_u64(ENV_SHUTDOWN_GRACE_SECS) {
+            config.shutdown_grace_secs = secs.min(60);
+        }
+        if let Some(max) = env_var_non_empty(ENV_MAX_AUTO_RESTARTS) {
+            match max.parse::<u64>() {
+                Ok(max) => config.max_auto_restarts = max.min(u32::MAX as u64) as u32,
+                _ => warn!("Ignoring invalid {}={:?}", ENV_MAX_AUTO_RESTARTS, max),
+            }
+        }
+        if let Some(factor) = env_positive_u64(ENV_LATENCY_DEGRADED_FACTOR) {
+            config.latency_degraded_factor = factor;
+        }
+        if let Some(ms) = env_positive_u64(ENV_METRICS_INTERVAL_MS) {
+            config.metrics_interval_ms = ms;
+        }
+        if let Some(reload) = env_flag(ENV_UVICORN_RELOAD) {
+            config.uvicorn_reload = reload;
+        }
+        if let Some(workers) = env_positive_u64(ENV_UVICORN_WORKERS) {
+            config.uvicorn_workers = workers.min(u32::MAX as u64) as u32;
+        }
+        if let Some(method) = env_var_non_empty(ENV_HEALTH_METHOD) {
+            match method.to_ascii_uppercase().as_str() {
+                "GET" => config.health_method = reqwest::Method::GET,
+                "HEAD" => config.health_method = reqwest::Method::HEAD,
+                _ => warn!(
+                    "Ignoring invalid {}={:?} (expected GET or HEAD)",
+                    ENV_HEALTH_METHOD, method
+                ),
+            }
+        }
+        if let Some(spec) = env_var_non_empty(ENV_HEALTH_STATUS) {
+            match parse_status_codes(&spec) {
+                Some(statuses) => config.health_statuses = statuses,
+                None => warn!(
+                    "Ignoring invalid {}={:?} (expected codes like 200,204 or 2xx)",
+                    ENV_HEALTH_STATUS, spec
+                ),
+            }
+        }
+        if let Some(path) = env_var_non_empty(ENV_HEALTH_PATH) {
+            if path.starts_with('/') {
+                config.health_path = path;
+            } else {
+                warn!(
+                    "Ignoring invalid {}={:?} (expected a path starting with /)",
+                    ENV_HEALTH_PATH, path
+                );
+            }
+        }
+        if let Some(scheme) = env_var_non_empty(ENV_BACKEND_SCHEME) {
+            match scheme.to_ascii_lowercase().as_str() {
+                scheme @ ("http" | "https") => config.scheme = scheme.to_string(),
+                _ => warn!(
+                    "Ignoring invalid {}={:?} (expected http or https)",
+                    ENV_BACKEND_SCHEME, scheme
+                ),
+            }
+        }
+        if let Some(host) = env_var_non_empty(ENV_BACKEND_HOST) {
+            config.host = host;
+        }
+        if let Some(port) = env_var_non_empty(ENV_BACKEND_URL_PORT) {
+            match port.parse::<u16>() {
+                Ok(port) if port > 0 => config.url_port = Some(port),
+                _ => warn!("Ignoring invalid {}={:?}", ENV_BACKEND_URL_PORT, port),
+            }
+        }
+        if let Some(accept) = env_flag(ENV_ACCEPT_INVALID_CERTS) {
+            config.accept_invalid_certs = accept;
+            if accept {
+                warn!(
+                    "{} is set: TLS certificates from the backend are not verified",
+                    ENV_ACCEPT_INVALID_CERTS
+                );
+            }
+        }
+        if config.health_method == reqwest::Method::HEAD {
+            if let Some(marker @ IdentityMarker::BodyField { .. }) = &config.identity_marker {
+                warn!(
+                    "Identity marker {} needs a response body, which HEAD health checks do not return; use a header marker",
+                    marker
+                );
+            }
+        }
+        if let Some(target) = env_var_non_empty(ENV_UVICORN_APP) {
+            config.uvicorn_app = target;
+        }
+        if let Some(limit) = env_positive_u64(ENV_PROXY_CONCURRENCY) {
+            config.proxy_concurrency = limit as usize;
+        }
+        if let Some(bytes) = env_positive_u64(ENV_MEMORY_LIMIT_BYTES) {
+            config.memory_limit_bytes = Some(bytes);
+        }
+        if let Some(detached) = env_flag(ENV_BACKEND_DETACHED) {
+            config.detached = detached;
+        }
+        if let Some(attach) = env_flag(ENV_ATTACH) {
+            config.attach = attach;
+        }
+        if let Some(reap) = env_flag(ENV_REAP_ORPHANS) {
+            config.reap_orphans = reap;
+        }
+        if let Some(mock) = env_flag(ENV_MOCK_BACKEND) {
+            config.mock = mock;
+        }
+        if config.mock
+            && (config.scheme != "http"
+                || config.host != BACKEND_HOST
+                || config.health_path != HEALTH_CHECK_PATH)
+        {
+            warn!("Ignoring backend URL overrides: the mock backend serves plain HTTP");
+            config.scheme = "http".to_string();
+            config.host = BACKEND_HOST.to_string();
+            config.health_path = HEALTH_CHECK_PATH.to_string();
+        }
+        if let Some(verify) = env_flag(ENV_VERIFY_UV) {
+            config.verify_uv = verify;
+        }
+        if let Some(launcher) = env_var_non_empty(ENV_LAUNCHER) {
+            match Launcher::from_name(&launcher) {
+                Some(launcher) => config.launcher = launcher,
+                None => warn!(
+                    "Ignoring invalid {}={:?} (expected auto, venv, uv or python)",
+                    ENV_LAUNCHER, launcher
+                ),
+            }
+        }
+        if let Some(order) = env_var_non_empty(ENV_LAUNCHER_ORDER) {
+            match parse_launcher_order(&order) {
+                Some(order) => config.launcher_order = order,
+                None => warn!(
+                    "Ignoring invalid {}={:?} (expected launchers among venv, uv and python)",
+                    ENV_LAUNCHER_ORDER, order
+                ),
+            }
+        }
+        if let Some(verify) = env_flag(ENV_VERIFY_SIDECAR_CHECKSUM) {
+            config.verify_sidecar_checksum = verify;
+        }
+        if let Some(unbuffered) = env_flag(ENV_PYTHON_UNBUFFERED) {
+            config.python_unbuffered = unbuffered;
+        }
+        if let Some(path) = env_var_non_empty(ENV_BACKEND_UDS) {
+            if config.mock {
+                warn!(
+                    "Ignoring {}: the mock backend only serves TCP",
+                    ENV_BACKEND_UDS
+                );
+            } else {
+                config.uds_path = Some(PathBuf::from(path));
+            }
+        }
+        if let Some(show) = env_flag(ENV_SHOW_BACKEND_CONSOLE) {
+            config.show_console = show;
+        }
+        if let Some(list) = env_var_non_empty(ENV_SELFTEST_ENDPOINTS) {
+            let endpoints = list
+                .split(',')
+                .map(|p| p.trim())
+                .filter(|p| p.starts_with('/'))
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>();
+            if endpoints.is_empty() {
+                warn!(
+                    "Ignoring {}={:?} (expected comma-separated paths starting with /)",
+                    ENV_SELFTEST_ENDPOINTS, list
+                );
+            } else {
+                config.selftest_endpoints = endpoints;
+            }
+        }
+        if config.reconnect_max_ms < config.reconnect_initial_ms {
+            config.reconnect_max_ms = config.reconnect_initial_ms;
         }
     }
 
-    stale_pids.len()
-}
+    /// Whether a health response status counts as healthy
+    fn is_healthy_status(&self, status: reqwest::StatusCode) -> bool {
+        self.health_statuses
+            .iter()
+            .any(|range| range.contains(&status.as_u16()))
+    }
 
-/// Get the platform-specific sidecar directory name
-fn get_sidecar_dir_name() -> &'static str {
-    #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
-    {
-        "sidecar-aarch64-apple-darwin"
+    /// Ports the backend may use, preferred port first
+    fn port_candidates(&self) -> RangeInclusive<u16> {
+        self.port..=self.port.saturating_add(PORT_SEARCH_SPAN)
     }
-    #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
-    {
-        "sidecar-x86_64-apple-darwin"
+
+    /// Extra backend arguments for the config file, validating it exists
+    fn config_file_args(&self) -> Result<Vec<String>, String> {
+        let Some(path) = &self.config_file else {
+            return Ok(Vec::new());
+        };
+        if !path.is_file() {
+            return Err(format!(
+                "Backend config file does not exist: {} (set via {})",
+                path.display(),
+                ENV_BACKEND_CONFIG
+            ));
+        }
+        info!("Passing backend config file {:?}", path);
+        Ok(vec![
+            self.config_flag.clone(),
+            path.to_string_lossy().to_string(),
+        ])
     }
-    #[cfg(all(target_os = "linux", target_arch = "aarch64"))]
-    {
-        "sidecar-aarch64-unknown-linux-gnu"
+}
+
+impl BackendConfig {
+    /// Overlay settings the user changed at runtime and persisted
+    fn apply_persisted(&mut self, settings: &PersistedSettings) {
+        // An explicit environment override beats the saved setting
+        if let Some(secs) = settings
+            .health_timeout_secs
+            .filter(|_| env_var_non_empty(ENV_HEALTH_TIMEOUT_SECS).is_none())
+        {
+            self.health_timeout_secs = secs.clamp(1, MAX_HEALTH_CHECK_TIMEOUT_SECS);
+        }
+        if let Some(port) = settings
+            .port
+            .filter(|_| env_var_non_empty(ENV_BACKEND_PORT).is_none())
+        {
+            self.port = port;
+        }
+        if let Some(level) = settings
+            .backend_log_level
+            .as_ref()
+            .filter(|_| env_var_non_empty(ENV_BACKEND_LOG_LEVEL).is_none())
+        {
+            self.backend_log_level = Some(level.clone());
+        }
+        if let Some(env) = settings
+            .extra_env
+            .as_ref()
+            .filter(|_| env_var_non_empty(ENV_BACKEND_ENV_FILE).is_none())
+        {
+            self.extra_env = env.clone();
+        }
     }
-    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
-    {
-        "sidecar-x86_64-unknown-linux-gnu"
+}
+
+/// How the dev-mode backend is started; bundled builds always run the sidecar
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Launcher {
+    /// The first of `launcher_order` that is available
+    #[default]
+    Auto,
+    /// The backend's virtualenv Python
+    Venv,
+    Uv,
+    /// `python3` or `python` from PATH, which needs uvicorn installed
+    Python,
+}
+
+impl Launcher {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "venv" => Some(Self::Venv),
+            "uv" => Some(Self::Uv),
+            "python" => Some(Self::Python),
+            _ => None,
+        }
     }
-    #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
-    {
-        "sidecar-x86_64-pc-windows-msvc"
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Venv => "venv",
+            Self::Uv => "uv",
+            Self::Python => "python",
+        }
     }
 }
 
-/// Get the platform-specific sidecar binary name
-fn get_sidecar_binary_name() -> &'static str {
-    #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
-    {
-        "backend-sidecar-aarch64-apple-darwin"
+/// Parse a comma-separated launcher list; `None` if a name is unknown, the
+/// list is empty or it contains `auto`
+fn parse_launcher_order(text: &str) -> Option<Vec<Launcher>> {
+    let order = text
+        .split(',')
+        .filter(|name| !name.trim().is_empty())
+        .map(Launcher::from_name)
+        .collect::<Option<Vec<_>>>()?;
+    (!order.is_empty() && !order.contains(&Launcher::Auto)).then_some(order)
+}
+
+/// An extra backend process, from a `[services.<name>]` table of `alproj.toml`
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceConfig {
+    /// Program and arguments, run in the backend directory (dev mode) or the
+    /// sidecar directory (bundled builds). A program given as a relative
+    /// path such as `./worker` is resolved against that directory, a bare
+    /// name is looked up in PATH.
+    pub command: Vec<String>,
+    /// URL checked by `check_service_health`; without one the service counts
+    /// as healthy while its process runs
+    #[serde(default)]
+    pub health_url: Option<String>,
+}
+
+/// Check a `[services.<name>]` table; the name ends up in a log file name
+fn validate_service(name: &str, service: &ServiceConfig) -> Result<(), String> {
+    if name == MAIN_SERVICE {
+        return Err(format!("{:?} is the main backend", MAIN_SERVICE));
     }
-    #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
     {
-        "backend-sidecar-x86_64-apple-darwin"
+        return Err("names may only contain letters, digits, - and _".to_string());
     }
-    #[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+    if service
+        .command
+        .first()
+        .map_or(true, |program| program.is_empty())
     {
-        "backend-sidecar-aarch64-unknown-linux-gnu"
+        return Err("command is empty".to_string());
     }
-    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
-    {
-        "backend-sidecar-x86_64-unknown-linux-gnu"
+    if let Some(url) = &service.health_url {
+        reqwest::Url::parse(url).map_err(|e| format!("invalid health_url {:?}: {}", url, e))?;
     }
-    #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
-    {
-        "backend-sidecar-x86_64-pc-windows-msvc.exe"
+    Ok(())
+}
+
+/// Contents of `alproj.toml`; every key is optional, unknown ones are errors
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    port: Option<u16>,
+    health_timeout_secs: Option<u64>,
+    health_path: Option<String>,
+    launcher: Option<Launcher>,
+    launcher_order: Option<Vec<Launcher>>,
+    workers: Option<u32>,
+    /// Extra environment variables for the backend
+    env: HashMap<String, String>,
+    /// Extra backend processes by name
+    services: HashMap<String, ServiceConfig>,
+}
+
+/// Read `alproj.toml` from `config_dir`; a missing file is silently
+/// skipped, an invalid one (including a misspelled key) skipped with a
+/// warning that names the problem
+fn read_config_file(config_dir: &Path) -> Option<FileConfig> {
+    let path = config_dir.join(CONFIG_FILE_NAME);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(e) => {
+            warn!("Ignoring config file {:?}: {}", path, e);
+            return None;
+        }
+    };
+    match toml::from_str(&text) {
+        Ok(file) => {
+            info!("Loaded backend settings from {:?}", path);
+            Some(file)
+        }
+        Err(e) => {
+            warn!("Ignoring invalid config file {:?}: {}", path, e);
+            None
+        }
     }
 }
 
-/// Start the Python backend sidecar process
-async fn start_sidecar(app: &tauri::AppHandle) -> Result<(ProcessHandle, Option<PathBuf>), String> {
-    if is_dev_mode() {
-        info!("Starting backend in development mode");
-        let backend_dir = get_dev_backend_dir(app)?;
+/// Settings changed from the UI that should survive a relaunch
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+struct PersistedSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    health_timeout_secs: Option<u64>,
+    /// Port of the last successful startup, preferred on the next one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_port: Option<u16>,
+    /// Preferred port chosen in the settings UI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    backend_log_level: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extra_env: Option<HashMap<String, String>>,
+    /// Log level of the desktop shell itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rust_log_level: Option<String>,
+}
 
-        info!("Backend directory: {:?}", backend_dir);
+fn settings_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_config_dir()
+        .map(|dir| dir.join(SETTINGS_FILE_NAME))
+        .map_err(|e| format!("Failed to get app config dir: {}", e))
+}
 
-        // Verify backend directory exists
-        if !backend_dir.exists() {
-            return Err(format!(
-                "Backend directory does not exist: {:?}",
-                backend_dir
-            ));
-        }
+/// Load persisted settings, falling back to defaults when missing or invalid
+fn load_persisted_settings(app: &tauri::AppHandle) -> PersistedSettings {
+    let Ok(path) = settings_path(app) else {
+        return PersistedSettings::default();
+    };
+    match fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+            warn!("Ignoring invalid settings file {:?}: {}", path, e);
+            PersistedSettings::default()
+        }),
+        Err(_) => PersistedSettings::default(),
+    }
+}
 
-        let log_path = resolve_backend_log_path(app);
-        if let Some(parent) = log_path.parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create backend log dir {:?}: {}", parent, e))?;
-        }
-        let stdout_log = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&log_path)
-            .map_err(|e| format!("Failed to open backend log file {:?}: {}", log_path, e))?;
-        let stderr_log = stdout_log
-            .try_clone()
-            .map_err(|e| format!("Failed to clone backend log file handle: {}", e))?;
-
-        let mut command = if let Some(python_path) = find_dev_python(&backend_dir) {
-            info!("Using virtualenv Python at {:?}", python_path);
-            let mut cmd = Command::new(python_path);
-            cmd.args([
-                "-m",
-                "uvicorn",
-                "app.main:app",
-                "--host",
-                BACKEND_HOST,
-                "--port",
-                &BACKEND_PORT.to_string(),
-            ]);
-            cmd
-        } else {
-            let uv_path =
-                find_uv_path().ok_or("Could not find uv. Please ensure uv is installed.")?;
-            warn!(
-                "Virtualenv Python not found under {:?}; falling back to uv run",
-                backend_dir.join(".venv")
-            );
-            let mut cmd = Command::new(uv_path);
-            cmd.args([
-                "run",
-                "uvicorn",
-                "app.main:app",
-                "--host",
-                BACKEND_HOST,
-                "--port",
-                &BACKEND_PORT.to_string(),
-            ]);
-            cmd
-        };
-
-        let child = command
-            .current_dir(&backend_dir)
-            .stdout(Stdio::from(stdout_log))
-            .stderr(Stdio::from(stderr_log))
-            .spawn()
-            .map_err(|e| format!("Failed to spawn uv process: {}", e))?;
+/// Read-modify-write the persisted settings file
+fn update_persisted_settings(
+    app: &tauri::AppHandle,
+    update: impl FnOnce(&mut PersistedSettings),
+) -> Result<(), String> {
+    let path = settings_path(app)?;
+    let mut settings = load_persisted_settings(app);
+    update(&mut settings);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create settings dir {:?}: {}", parent, e))?;
+    }
+    let json = serde_json::to_vec_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write settings {:?}: {}", path, e))
+}
 
-        info!("Backend process started with PID: {:?}", child.id());
-        info!("Backend log path: {:?}", log_path);
+/// Detached backend left running by a previous GUI run
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+struct DetachedRecord {
+    pid: u32,
+    port: u16,
+    started_at_ms: u64,
+}
 
-        Ok((ProcessHandle::StdChild(child), Some(log_path)))
-    } else {
-        // Production mode: use bundled sidecar from resources
-        // The sidecar is built with PyInstaller --onedir and needs _internal next to it
-        info!("Starting backend in production mode with bundled sidecar");
+fn detached_record_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(DETACHED_RECORD_FILE_NAME))
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
 
-        let resource_dir = app
-            .path()
-            .resource_dir()
-            .map_err(|e| format!("Failed to get resource dir: {}", e))?;
+fn read_detached_record(app: &tauri::AppHandle) -> Option<DetachedRecord> {
+    let bytes = fs::read(detached_record_path(app).ok()?).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
 
-        // Flatpak can place resources under /app/lib/<name>, so probe multiple roots.
-        #[cfg(target_os = "linux")]
-        let resource_roots = {
-            let mut roots = vec![resource_dir];
-            roots.push(PathBuf::from("/app/lib/alproj-gui"));
-            roots.push(PathBuf::from("/app/lib/com.alproj.gui"));
-            roots
-        };
-        #[cfg(not(target_os = "linux"))]
-        let resource_roots = vec![resource_dir];
+fn write_detached_record(app: &tauri::AppHandle, record: &DetachedRecord) -> Result<(), String> {
+    let path = detached_record_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data dir {:?}: {}", parent, e))?;
+    }
+    let json = serde_json::to_vec_pretty(record)
+        .map_err(|e| format!("Failed to serialize detached backend record: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
 
-        let mut selected_sidecar: Option<(PathBuf, PathBuf)> = None;
-        for root in &resource_roots {
-            let sidecar_dir = root.join("binaries").join(get_sidecar_dir_name());
-            let sidecar_path = sidecar_dir.join(get_sidecar_binary_name());
-            if sidecar_path.exists() {
-                selected_sidecar = Some((sidecar_dir, sidecar_path));
-                break;
-            }
+fn remove_detached_record(app: &tauri::AppHandle) {
+    if let Ok(path) = detached_record_path(app) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Lifecycle state of the backend
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendState {
+    /// Not started, or stopped on request
+    Stopped,
+    /// Spawned and waiting for the health check
+    Starting,
+    /// Answering health checks
+    Ready,
+    /// Stopped answering after being ready; retrying with backoff
+    Reconnecting,
+    /// Did not recover after being lost
+    Crashed,
+    /// Never became ready
+    Failed,
+}
+
+/// Process found listening on a TCP port
+#[derive(Clone, Debug, serde::Serialize)]
+struct PortOwner {
+    pid: u32,
+    name: Option<String>,
+}
+
+/// Best-effort lookup of the process listening on `port` via platform tools
+fn find_port_owner(port: u16) -> Option<PortOwner> {
+    let pid = find_listening_pid(port)?;
+    let mut sys = System::new();
+    sys.refresh_processes(
+        sysinfo::ProcessesToUpdate::Some(&[Pid::from_u32(pid)]),
+        true,
+    );
+    let name = sys
+        .process(Pid::from_u32(pid))
+        .map(|p| p.name().to_string_lossy().to_string());
+    Some(PortOwner { pid, name })
+}
+
+impl std::fmt::Display for PortOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "PID {} ({})", self.pid, name),
+            None => write!(f, "PID {}", self.pid),
         }
+    }
+}
 
-        let (sidecar_dir, sidecar_path) = selected_sidecar.ok_or_else(|| {
-            let roots = resource_roots
-                .iter()
-                .map(|p| p.display().to_string())
-                .collect::<Vec<_>>()
-                .join(", ");
-            format!(
-                "Sidecar binary not found. checked_roots=[{}], sidecar_dir_name={}, sidecar_binary_name={}",
-                roots,
-                get_sidecar_dir_name(),
-                get_sidecar_binary_name()
-            )
+#[cfg(target_os = "linux")]
+fn find_listening_pid(port: u16) -> Option<u32> {
+    // Find the socket inode in /proc/net/tcp{,6}, then the process holding it
+    const TCP_LISTEN: &str = "0A";
+    let port_hex = format!("{:04X}", port);
+    let inode = ["/proc/net/tcp", "/proc/net/tcp6"]
+        .iter()
+        .filter_map(|table| fs::read_to_string(table).ok())
+        .flat_map(|text| text.lines().skip(1).map(str::to_string).collect::<Vec<_>>())
+        .find_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let local_port = fields.get(1)?.rsplit(':').next()?;
+            (local_port == port_hex && *fields.get(3)? == TCP_LISTEN)
+                .then(|| fields.get(9).map(|s| s.to_string()))
+                .flatten()
         })?;
+    let target = format!("socket:[{}]", inode);
 
-        info!("Sidecar directory: {:?}", sidecar_dir);
-        info!("Sidecar path: {:?}", sidecar_path);
+    fs::read_dir("/proc").ok()?.flatten().find_map(|entry| {
+        let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+        fs::read_dir(entry.path().join("fd"))
+            .ok()?
+            .flatten()
+            .any(|fd| {
+                fs::read_link(fd.path())
+                    .map(|link| link.to_string_lossy() == target)
+                    .unwrap_or(false)
+            })
+            .then_some(pid)
+    })
+}
 
-        // Start the sidecar process
-        // Must run from sidecar_dir so it can find _internal
-        let log_path = resolve_backend_log_path(app);
-        if let Some(parent) = log_path.parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create backend log dir {:?}: {}", parent, e))?;
-        }
-        let stdout_log = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&log_path)
-            .map_err(|e| format!("Failed to open backend log file {:?}: {}", log_path, e))?;
-        let stderr_log = stdout_log
-            .try_clone()
-            .map_err(|e| format!("Failed to clone backend log file handle: {}", e))?;
+#[cfg(target_os = "macos")]
+fn find_listening_pid(port: u16) -> Option<u32> {
+    let output = Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{}", port), "-sTCP:LISTEN", "-t"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.trim().parse::<u32>().ok())
+}
 
-        let mut command = Command::new(&sidecar_path);
-        command
-            .args(["--host", BACKEND_HOST, "--port", &BACKEND_PORT.to_string()])
-            .current_dir(&sidecar_dir)
-            .stdout(Stdio::from(stdout_log))
-            .stderr(Stdio::from(stderr_log));
+#[cfg(windows)]
+fn find_listening_pid(port: u16) -> Option<u32> {
+    let output = Command::new("netstat")
+        .args(["-ano", "-p", "TCP"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .ok()?;
+    let suffix = format!(":{}", port);
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            // Proto  Local Address  Foreign Address  State  PID
+            if fields.len() == 5 && fields[1].ends_with(&suffix) && fields[3] == "LISTENING" {
+                fields[4].parse::<u32>().ok()
+            } else {
+                None
+            }
+        })
+}
 
-        #[cfg(windows)]
-        command.creation_flags(CREATE_NO_WINDOW);
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn find_listening_pid(_port: u16) -> Option<u32> {
+    None
+}
 
-        let child = command
-            .spawn()
-            .map_err(|e| format!("Failed to spawn sidecar: {}", e))?;
+/// Application state for managing the Python backend sidecar
+pub struct AppState {
+    /// Backend process handles by service name; the backend the other
+    /// commands manage is `MAIN_SERVICE`, the rest come from `services`
+    pub sidecars: Mutex<HashMap<String, ProcessHandle>>,
+    /// Backend ready flag
+    pub backend_ready: Mutex<bool>,
+    /// Sidecar stdout log file path
+    pub backend_log_path: Mutex<Option<PathBuf>>,
+    /// Sidecar stderr log file path (uvicorn's logging and tracebacks)
+    pub backend_stderr_log_path: Mutex<Option<PathBuf>>,
+    /// Recent lifecycle events, for frontends that missed the emitted ones
+    event_journal: Mutex<EventJournal>,
+    /// Effective backend launch settings
+    pub config: Mutex<BackendConfig>,
+    /// URL pieces from the config, fixed for the app's lifetime
+    backend_scheme: String,
+    backend_host: String,
+    /// Fixed port in backend URLs instead of the bind port
+    backend_url_port: Option<u16>,
+    health_path: String,
+    /// Current lifecycle state
+    pub backend_state: Mutex<BackendState>,
+    /// Bumped whenever the backend is stopped, so background monitors of a
+    /// previous run can tell they are stale and exit
+    lifecycle_generation: AtomicU64,
+    /// HTTP client shared by backend requests (keeps the connection pool warm)
+    pub http_client: reqwest::Client,
+    /// Plain TCP client for the `health_url` of extra services;
+    /// `http_client` may be bound to the main backend's Unix socket
+    service_client: reqwest::Client,
+    /// Most recent health probe result
+    last_health: Mutex<Option<HealthSample>>,
+    /// Whether the liveness watchdog last saw the backend answering (`None`
+    /// before its first check)
+    backend_alive: Mutex<Option<bool>>,
+    /// Health latencies seen by the monitor since the backend became ready
+    latency_trend: Mutex<LatencyTrend>,
+    /// When the backend last became ready (cleared when it stops)
+    ready_since: Mutex<Option<std::time::Instant>>,
+    /// Wall-clock time (ms since the Unix epoch) of `ready_since`
+    ready_at_ms: Mutex<Option<u64>>,
+    /// Number of times the backend has been restarted
+    pub restart_count: AtomicU32,
+    /// Automatic crash restarts since the backend was last stable
+    auto_restarts: AtomicU32,
+    /// How long the most recent successful start took, spawn to ready
+    last_startup_ms: Mutex<Option<u64>>,
+    /// Process table kept between metrics samples, which CPU usage is
+    /// measured against
+    metrics_system: Mutex<System>,
+    /// Serializes restarts so only one stop/start sequence runs at a time
+    lifecycle_lock: Mutex<()>,
+    /// Build metadata of the running backend (cleared when it stops)
+    backend_build_info: Mutex<Option<BackendBuildInfo>>,
+    /// Background stream tasks that emit events until cancelled
+    streams: Mutex<StreamRegistry>,
+    /// uv executable that passed `find_uv_path`, reused by later starts
+    uv_path: Mutex<Option<String>>,
+    /// Launcher of the running dev-mode backend
+    active_launcher: Mutex<Option<Launcher>>,
+    /// Log files kept open by `open_log_reader`
+    log_readers: Mutex<LogReaders>,
+    /// Line starts of the backend log, for `read_backend_log_lines`
+    log_line_index: Mutex<LineIndex>,
+    /// Bounds concurrent `backend_request` calls; extra callers wait for a permit
+    proxy_limiter: tokio::sync::Semaphore,
+    proxy_limit: usize,
+    /// `backend_request` calls waiting for a permit
+    proxy_queued: AtomicUsize,
+    /// `backend_request` calls sent, and those that got no response
+    proxy_requests: AtomicU64,
+    proxy_errors: AtomicU64,
+    /// Port of the current (or most recent) backend
+    backend_port: AtomicU16,
+    /// Output of the most recent start, from spawn until ready or failed
+    startup_log: Mutex<StartupLog>,
+    /// Latest startup progress; readable at any time, unlike `backend-progress` events
+    startup_progress: tokio::sync::watch::Sender<StartupProgress>,
+    /// Cancel flag of the restart countdown after a crash, while one runs
+    pending_restart: Mutex<Option<Arc<AtomicBool>>>,
+    /// Set by `cancel_backend_startup`; `wait_for_backend` gives up when it
+    /// sees it. Cleared whenever a launch begins.
+    startup_cancelled: AtomicBool,
+    /// Set once the app is quitting, so window close and `shutdown_and_exit`
+    /// don't both tear down the backend
+    shutting_down: AtomicBool,
+}
 
-        info!("Backend process started with PID: {:?}", child.id());
-        info!("Backend log path: {:?}", log_path);
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StartupPhase {
+    /// No start has been attempted yet
+    #[default]
+    Idle,
+    Spawning,
+    WaitingForHealth,
+    Ready,
+    Failed,
+}
+
+/// Where the most recent backend start is, published via `startup_progress`
+/// and the `backend-progress` event
+#[derive(Clone, Debug, Default, serde::Serialize)]
+struct StartupProgress {
+    phase: StartupPhase,
+    elapsed_ms: u64,
+    /// Health polls made so far
+    attempts: u32,
+    timeout_secs: u64,
+    /// Result of the last health poll, or the error once failed
+    last_outcome: Option<String>,
+}
+
+/// Kinds of background stream; at most one of each runs at a time, so
+/// stdout and stderr can each have their own log stream
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StreamKind {
+    Log(OutputStream),
+    Health,
+    Metrics,
+}
+
+/// Active stream tasks by id. Each task polls its cancel flag in its loop
+/// and exits once it is set; `spawn_stream` removes the entry of a task
+/// that has ended, whether cancelled or on its own.
+#[derive(Default)]
+struct StreamRegistry {
+    next_id: u64,
+    active: HashMap<u64, (StreamKind, Arc<AtomicBool>)>,
+}
+
+impl StreamRegistry {
+    /// Register a new stream, cancelling any running stream of the same kind
+    fn start(&mut self, kind: StreamKind) -> (u64, Arc<AtomicBool>) {
+        self.cancel(Some(kind));
+        self.next_id += 1;
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.active.insert(self.next_id, (kind, cancel.clone()));
+        (self.next_id, cancel)
+    }
+
+    /// Cancel streams of `kind` (all streams for `None`), returning how many
+    fn cancel(&mut self, kind: Option<StreamKind>) -> usize {
+        let before = self.active.len();
+        self.active.retain(|_, (stream_kind, cancel)| {
+            if kind.map_or(true, |kind| kind == *stream_kind) {
+                cancel.store(true, Ordering::SeqCst);
+                false
+            } else {
+                true
+            }
+        });
+        before - self.active.len()
+    }
 
-        Ok((ProcessHandle::StdChild(child), Some(log_path)))
+    /// Forget the stream `id` once its task has ended
+    fn finish(&mut self, id: u64) {
+        self.active.remove(&id);
     }
 }
 
-/// Wait for the backend to become ready by polling the health endpoint
-async fn wait_for_backend(state: &Arc<AppState>) -> Result<(), String> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(5))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+/// Run the stream task registered as `id`, removing it from the registry
+/// when the task ends
+fn spawn_stream(
+    state: Arc<AppState>,
+    id: u64,
+    task: impl std::future::Future<Output = ()> + Send + 'static,
+) {
+    tauri::async_runtime::spawn(async move {
+        task.await;
+        state.streams.lock().await.finish(id);
+    });
+}
 
-    let start = std::time::Instant::now();
-    let timeout = Duration::from_secs(HEALTH_CHECK_TIMEOUT_SECS);
-    let health_urls = [HEALTH_CHECK_URL, HEALTH_CHECK_URL_LOCALHOST];
+/// A backend log kept open between reads, with the offset of the next one
+struct LogReader {
+    path: PathBuf,
+    file: fs::File,
+    offset: usize,
+}
 
-    info!(
-        "Waiting for backend to become ready at {}",
-        HEALTH_CHECK_URL
-    );
+/// Open log readers by id
+#[derive(Default)]
+struct LogReaders {
+    next_id: u64,
+    open: HashMap<u64, LogReader>,
+}
 
-    while start.elapsed() < timeout {
-        if let Some(exit_error) = check_sidecar_exited(state).await {
-            return Err(exit_error);
+/// Byte offsets of every `LOG_INDEX_STRIDE`th line of the backend log,
+/// extended as the log grows and rebuilt when it was truncated or replaced
+#[derive(Default)]
+struct LineIndex {
+    path: Option<PathBuf>,
+    /// Start of lines 0, `LOG_INDEX_STRIDE`, `2 * LOG_INDEX_STRIDE`, ...
+    checkpoints: Vec<u64>,
+    /// Complete (newline-terminated) lines scanned
+    newlines: usize,
+    /// Start of the line after the last newline
+    last_line_start: u64,
+    /// How much of the file has been scanned
+    indexed_len: u64,
+    /// First bytes of the indexed file, compared to spot a replaced log
+    head: Vec<u8>,
+}
+
+impl LineIndex {
+    /// Bring the index up to date with the file at `path` (blocking)
+    fn update(&mut self, path: &Path) -> std::io::Result<()> {
+        let mut file = fs::File::open(path)?;
+        let len = file.metadata()?.len();
+        let mut head = Vec::new();
+        (&mut file)
+            .take(LOG_INDEX_HEAD_BYTES as u64)
+            .read_to_end(&mut head)?;
+        let shared = head.len().min(self.head.len());
+        let replaced = self.path.as_deref() != Some(path)
+            || len < self.indexed_len
+            || head[..shared] != self.head[..shared];
+        if replaced {
+            *self = LineIndex {
+                path: Some(path.to_path_buf()),
+                checkpoints: vec![0],
+                ..Default::default()
+            };
+        }
+        if self.head.len() < head.len() {
+            self.head = head;
         }
 
-        for url in health_urls {
-            match client.get(url).send().await {
-                Ok(response) => {
-                    if response.status().is_success() {
-                        info!("Backend is ready at {}", url);
-                        return Ok(());
-                    }
-                    warn!(
-                        "Backend returned non-success status at {}: {}",
-                        url,
-                        response.status()
-                    );
-                }
-                Err(e) => {
-                    // Connection refused is expected while backend is starting
-                    if !e.is_connect() {
-                        warn!("Health check failed at {}: {}", url, e);
+        file.seek(SeekFrom::Start(self.indexed_len))?;
+        let mut buffer = [0u8; LOG_READ_BUFFER_BYTES];
+        let mut reader = file.take(len - self.indexed_len);
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            for (i, byte) in buffer[..read].iter().enumerate() {
+                if *byte == b'\n' {
+                    self.newlines += 1;
+                    self.last_line_start = self.indexed_len + i as u64 + 1;
+                    if self.newlines % LOG_INDEX_STRIDE == 0 {
+                        self.checkpoints.push(self.last_line_start);
                     }
                 }
             }
+            self.indexed_len += read as u64;
         }
+        Ok(())
+    }
 
-        sleep(Duration::from_millis(HEALTH_CHECK_INTERVAL_MS)).await;
+    /// Lines in the index; a last line without a newline yet counts too
+    fn line_count(&self) -> usize {
+        self.newlines + usize::from(self.indexed_len > self.last_line_start)
     }
 
-    let mut error_message = format!(
-        "Backend failed to start within {} seconds",
-        HEALTH_CHECK_TIMEOUT_SECS
-    );
-    if let Some(log_tail) = read_backend_log_tail(state, 80).await {
-        error_message.push('\n');
-        error_message.push_str(&log_tail);
+    /// Where to start reading for `line`: the offset of the nearest indexed
+    /// line at or before it and how many lines to skip from there
+    fn seek_point(&self, line: usize) -> Option<(u64, usize)> {
+        if line >= self.line_count() {
+            return None;
+        }
+        let offset = *self.checkpoints.get(line / LOG_INDEX_STRIDE)?;
+        Some((offset, line % LOG_INDEX_STRIDE))
     }
-    Err(error_message)
 }
 
-/// Stop the sidecar process gracefully
-async fn stop_sidecar(state: &AppState) {
-    let mut sidecar = state.sidecar.lock().await;
-    if let Some(handle) = sidecar.take() {
-        info!("Stopping backend sidecar...");
-        if let Err(e) = handle.kill() {
-            error!("Failed to kill sidecar process: {}", e);
-        } else {
-            info!("Backend sidecar stopped");
+/// Skip `skip` lines of `reader`, then return up to `count` lines without
+/// their line endings
+fn read_lines_after(reader: impl Read, skip: usize, count: usize) -> std::io::Result<Vec<Vec<u8>>> {
+    use std::io::BufRead;
+
+    let mut reader = std::io::BufReader::new(reader);
+    let mut lines = Vec::with_capacity(count);
+    let mut line = Vec::new();
+    for n in 0..skip + count {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        if n < skip {
+            continue;
+        }
+        if line.last() == Some(&b'\n') {
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
         }
+        lines.push(std::mem::take(&mut line));
     }
+    Ok(lines)
 }
 
-/// Initialize the Tauri application
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    // Initialize logger
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+/// Build fingerprint reported by the backend; fields it omits stay `None`
+#[derive(Clone, Debug, Default, serde::Serialize)]
+struct BackendBuildInfo {
+    version: Option<String>,
+    commit: Option<String>,
+    build_date: Option<String>,
+    python_version: Option<String>,
+}
 
-    info!("Starting ALPROJ GUI");
+impl BackendBuildInfo {
+    fn from_json(body: &serde_json::Value) -> Self {
+        let field = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|key| body.get(*key)?.as_str().map(str::to_string))
+        };
+        Self {
+            version: field(&["version"]),
+            commit: field(&["commit", "git_commit", "git_sha"]),
+            build_date: field(&["build_date", "built_at"]),
+            python_version: field(&["python_version", "python"]),
+        }
+    }
+}
 
-    tauri::Builder::default()
-        .plugin(tauri_plugin_shell::init())
-        .plugin(tauri_plugin_dialog::init())
-        .manage(Arc::new(AppState::default()))
-        .setup(|app| {
-            // Open devtools in debug mode
-            #[cfg(debug_assertions)]
-            {
-                if let Some(window) = app.get_webview_window("main") {
-                    window.open_devtools();
-                }
-            }
+/// Outcome of a health probe
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum HealthStatus {
+    /// The health endpoint answered with a success status
+    Healthy,
+    /// The backend answered, but not with a success status
+    Unhealthy,
+    /// No response (not running, refused or timed out)
+    Unreachable,
+}
 
-            // Start Python sidecar
-            let app_handle = app.handle().clone();
-            let state = app.state::<Arc<AppState>>().inner().clone();
+/// Result of a single health probe
+#[derive(Clone, Debug)]
+struct HealthSample {
+    checked_at: std::time::Instant,
+    status: HealthStatus,
+    latency_ms: u64,
+    version: Option<String>,
+}
 
-            tauri::async_runtime::spawn(async move {
-                if is_dev_mode() {
-                    match get_dev_backend_dir(&app_handle) {
-                        Ok(backend_dir) => {
-                            let cleaned = cleanup_stale_backend_processes(&backend_dir);
-                            if cleaned > 0 {
-                                warn!("Cleaned up {} stale backend process(es)", cleaned);
-                                sleep(Duration::from_millis(300)).await;
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Skipping stale backend cleanup: {}", e);
-                        }
-                    }
-                }
+/// Ring buffer of liveness-check latencies with a baseline taken from the
+/// first checks after the backend became ready
+#[derive(Debug, Default)]
+struct LatencyTrend {
+    samples: VecDeque<u64>,
+    baseline: Vec<u64>,
+    /// Whether `backend-degraded` was emitted and the latency hasn't recovered
+    degraded: bool,
+}
 
-                match start_sidecar(&app_handle).await {
-                    Ok((child, log_path)) => {
-                        // Store the child process handle
-                        *state.sidecar.lock().await = Some(child);
-                        *state.backend_log_path.lock().await = log_path;
+/// Latency figures reported in `backend-heartbeat` and `get_backend_info`
+#[derive(Clone, Debug, serde::Serialize)]
+struct LatencySummary {
+    /// Mean of the recent samples
+    average_ms: f64,
+    /// Mean of the first samples after ready, once there are enough
+    baseline_ms: Option<f64>,
+    /// Slope of the recent samples; positive means the backend is slowing
+    trend_ms_per_min: f64,
+    samples: usize,
+}
 
-                        // Wait for backend to be ready
-                        match wait_for_backend(&state).await {
-                            Ok(()) => {
-                                *state.backend_ready.lock().await = true;
-                                info!("Backend initialization complete");
+impl LatencyTrend {
+    fn record(&mut self, latency_ms: u64) {
+        if self.baseline.len() < LATENCY_BASELINE_SAMPLES {
+            self.baseline.push(latency_ms);
+        }
+        if self.samples.len() == LATENCY_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency_ms);
+    }
 
-                                // Emit event to frontend
-                                if let Err(e) = app_handle.emit("backend-ready", true) {
-                                    error!("Failed to emit backend-ready event: {}", e);
-                                }
-                            }
-                            Err(e) => {
-                                error!("Backend failed to start: {}", e);
-                                // Emit error event to frontend
-                                if let Err(e) = app_handle.emit("backend-error", e.clone()) {
-                                    error!("Failed to emit backend-error event: {}", e);
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!("Failed to start sidecar: {}", e);
-                        // Emit error event to frontend
-                        if let Err(emit_err) = app_handle.emit("backend-error", e.clone()) {
-                            error!("Failed to emit backend-error event: {}", emit_err);
-                        }
-                    }
-                }
-            });
+    fn summary(&self) -> Option<LatencySummary> {
+        let n = self.samples.len();
+        if n == 0 {
+            return None;
+        }
+        let average_ms = self.samples.iter().sum::<u64>() as f64 / n as f64;
+        let baseline_ms = (self.baseline.len() == LATENCY_BASELINE_SAMPLES)
+            .then(|| self.baseline.iter().sum::<u64>() as f64 / self.baseline.len() as f64);
 
-            Ok(())
-        })
-        .on_window_event(|window, event| {
-            // Handle window close to stop sidecar
-            if let tauri::WindowEvent::CloseRequested { .. } = event {
-                let state = window.state::<Arc<AppState>>().inner().clone();
-                tauri::async_runtime::block_on(async {
-                    stop_sidecar(&state).await;
-                });
-            }
+        // Least-squares slope over the sample index, scaled to a minute
+        let mean_x = (n - 1) as f64 / 2.0;
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for (i, latency) in self.samples.iter().enumerate() {
+            let dx = i as f64 - mean_x;
+            covariance += dx * (*latency as f64 - average_ms);
+            variance += dx * dx;
+        }
+        let slope_per_check = if variance > 0.0 {
+            covariance / variance
+        } else {
+            0.0
+        };
+        Some(LatencySummary {
+            average_ms,
+            baseline_ms,
+            trend_ms_per_min: slope_per_check * 60_000.0 / LIVENESS_CHECK_INTERVAL_MS as f64,
+            samples: n,
         })
-        .invoke_handler(tauri::generate_handler![
-            greet,
-            get_backend_status,
-            check_backend_health,
-            get_backend_log_cursor,
-            read_backend_log_chunk,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+    }
+
+    /// Update the degraded flag, returning the summary when the backend has
+    /// just crossed `factor` times its baseline
+    fn check_degraded(&mut self, factor: u64) -> Option<LatencySummary> {
+        let summary = self.summary()?;
+        let baseline = summary.baseline_ms?.max(LATENCY_BASELINE_FLOOR_MS);
+        let degraded = summary.average_ms > baseline * factor as f64;
+        let crossed = degraded && !self.degraded;
+        self.degraded = degraded;
+        crossed.then_some(summary)
+    }
 }
 
-/// Simple greeting command for testing
-#[tauri::command]
-fn greet(name: &str) -> String {
-    format!("Hello, {}! Welcome to ALPROJ GUI.", name)
+/// Client builder for talking to the backend; connects over the Unix
+/// socket when `uds_path` is set (URL host and port are then ignored)
+fn backend_client_builder(config: &BackendConfig) -> reqwest::ClientBuilder {
+    let builder =
+        reqwest::Client::builder().danger_accept_invalid_certs(config.accept_invalid_certs);
+    #[cfg(unix)]
+    if let Some(path) = &config.uds_path {
+        return builder.unix_socket(path.clone());
+    }
+    builder
 }
 
-/// Check if the Python backend is running (from state)
-#[tauri::command]
-async fn get_backend_status(state: tauri::State<'_, Arc<AppState>>) -> Result<String, String> {
-    let ready = *state.backend_ready.lock().await;
-    if ready {
-        Ok("connected".to_string())
-    } else {
-        Ok("connecting".to_string())
+/// `--workers N` when more than one uvicorn worker is configured; uvicorn
+/// can't combine workers with `--reload`, so reload wins
+fn uvicorn_worker_args(config: &BackendConfig) -> Vec<String> {
+    if config.uvicorn_workers <= 1 {
+        return Vec::new();
+    }
+    if config.uvicorn_reload && is_dev_mode() {
+        warn!(
+            "Ignoring {}={} because {} is set",
+            ENV_UVICORN_WORKERS, config.uvicorn_workers, ENV_UVICORN_RELOAD
+        );
+        return Vec::new();
     }
+    info!("Running uvicorn with {} workers", config.uvicorn_workers);
+    vec!["--workers".to_string(), config.uvicorn_workers.to_string()]
 }
 
-/// Check backend health by calling the health endpoint
-#[tauri::command]
-async fn check_backend_health() -> Result<serde_json::Value, String> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(5))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+/// Arguments telling the backend where to listen
+fn backend_listen_args(config: &BackendConfig, port: u16) -> Vec<String> {
+    match &config.uds_path {
+        Some(path) => vec!["--uds".to_string(), path.to_string_lossy().to_string()],
+        None => vec![
+            "--host".to_string(),
+            BACKEND_HOST.to_string(),
+            "--port".to_string(),
+            port.to_string(),
+        ],
+    }
+}
 
-    let response = client
-        .get(HEALTH_CHECK_URL)
-        .send()
-        .await
-        .map_err(|e| format!("Health check request failed: {}", e))?;
+/// Make sure the backend can create its socket at `path`: create the parent
+/// directory and remove a stale socket left by an earlier run
+fn prepare_uds_path(path: &Path) -> Result<(), String> {
+    #[cfg(windows)]
+    {
+        Err(format!(
+            "{} is set to {:?}, but Unix domain sockets are not supported on Windows; unset it to use TCP",
+            ENV_BACKEND_UDS, path
+        ))
+    }
+    #[cfg(not(windows))]
+    {
+        use std::os::unix::fs::FileTypeExt;
 
-    if !response.status().is_success() {
-        return Err(format!(
-            "Health check failed with status: {}",
-            response.status()
-        ));
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create socket dir {:?}: {}", parent, e))?;
+        }
+        match fs::symlink_metadata(path) {
+            Ok(meta) if meta.file_type().is_socket() => fs::remove_file(path)
+                .map_err(|e| format!("Failed to remove stale socket {:?}: {}", path, e)),
+            Ok(_) => Err(format!(
+                "{} points at {:?}, which exists and is not a socket",
+                ENV_BACKEND_UDS, path
+            )),
+            Err(_) => Ok(()),
+        }
     }
+}
 
-    response
-        .json::<serde_json::Value>()
-        .await
-        .map_err(|e| format!("Failed to parse health check response: {}", e))
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new(BackendConfig::default())
+    }
 }
 
-#[tauri::command]
-async fn get_backend_log_cursor(state: tauri::State<'_, Arc<AppState>>) -> Result<usize, String> {
-    let log_path = state.backend_log_path.lock().await.clone();
-    let Some(path) = log_path else {
-        return Ok(0);
-    };
+impl AppState {
+    pub fn new(config: BackendConfig) -> Self {
+        let proxy_limit = config.proxy_concurrency.max(1);
+        let port = config.port;
+        let backend_scheme = config.scheme.clone();
+        let backend_host = config.host.clone();
+        let backend_url_port = config.url_port;
+        let health_path = config.health_path.clone();
+        let http_client = backend_client_builder(&config)
+            .timeout(Duration::from_secs(HTTP_CLIENT_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_else(|e| {
+                warn!("Failed to configure HTTP client, using defaults: {}", e);
+                reqwest::Client::new()
+            });
+        let service_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(HTTP_CLIENT_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_else(|e| {
+                warn!(
+                    "Failed to configure service HTTP client, using defaults: {}",
+                    e
+                );
+                reqwest::Client::new()
+            });
+        Self {
+            sidecars: Mutex::new(HashMap::new()),
+            backend_ready: Mutex::new(false),
+            backend_log_path: Mutex::new(None),
+            backend_stderr_log_path: Mutex::new(None),
+            event_journal: Mutex::new(EventJournal::default()),
+            config: Mutex::new(config),
+            backend_scheme,
+            backend_host,
+            backend_url_port,
+            health_path,
+            backend_state: Mutex::new(BackendState::Stopped),
+            lifecycle_generation: AtomicU64::new(0),
+            http_client,
+            service_client,
+            last_health: Mutex::new(None),
+            backend_alive: Mutex::new(None),
+            latency_trend: Mutex::new(LatencyTrend::default()),
+            ready_since: Mutex::new(None),
+            ready_at_ms: Mutex::new(None),
+            restart_count: AtomicU32::new(0),
+            auto_restarts: AtomicU32::new(0),
+            last_startup_ms: Mutex::new(None),
+            metrics_system: Mutex::new(System::new()),
+            lifecycle_lock: Mutex::new(()),
+            backend_build_info: Mutex::new(None),
+            streams: Mutex::new(StreamRegistry::default()),
+            uv_path: Mutex::new(None),
+            active_launcher: Mutex::new(None),
+            log_readers: Mutex::new(LogReaders::default()),
+            log_line_index: Mutex::new(LineIndex::default()),
+            proxy_limiter: tokio::sync::Semaphore::new(proxy_limit),
+            proxy_limit,
+            proxy_queued: AtomicUsize::new(0),
+            proxy_requests: AtomicU64::new(0),
+            proxy_errors: AtomicU64::new(0),
+            backend_port: AtomicU16::new(port),
+            startup_log: Mutex::new(StartupLog::default()),
+            shutting_down: AtomicBool::new(false),
+            pending_restart: Mutex::new(None),
+            startup_cancelled: AtomicBool::new(false),
+            startup_progress: tokio::sync::watch::Sender::new(StartupProgress::default()),
+        }
+    }
 
-    let meta = fs::metadata(&path)
-        .map_err(|e| format!("Failed to read backend log metadata {:?}: {}", path, e))?;
-    Ok(meta.len() as usize)
+    /// Base URL of the backend HTTP API
+    fn backend_base_url(&self) -> String {
+        self.base_url_at(&self.backend_host, self.backend_port.load(Ordering::SeqCst))
+    }
+
+    /// `scheme://host:port` of a backend bound to `port`; the configured
+    /// URL port wins when the backend sits behind a proxy
+    fn base_url_at(&self, host: &str, port: u16) -> String {
+        let port = self.backend_url_port.unwrap_or(port);
+        format!("{}://{}:{}", self.backend_scheme, host, port)
+    }
+
+    fn health_url(&self) -> String {
+        format!("{}{}", self.backend_base_url(), self.health_path)
+    }
+
+    /// Health URLs probed during startup: the configured host and, when it
+    /// is a loopback name, the other one (`127.0.0.1` / `localhost`)
+    fn startup_health_urls(&self) -> Vec<String> {
+        let mut urls = vec![self.health_url()];
+        let alternate = match self.backend_host.as_str() {
+            "127.0.0.1" => Some("localhost"),
+            "localhost" => Some("127.0.0.1"),
+            _ => None,
+        };
+        if let Some(host) = alternate {
+            let port = self.backend_port.load(Ordering::SeqCst);
+            urls.push(format!(
+                "{}{}",
+                self.base_url_at(host, port),
+                self.health_path
+            ));
+        }
+        urls
+    }
+
+    /// Log file of one backend output stream
+    async fn log_path(&self, stream: OutputStream) -> Option<PathBuf> {
+        match stream {
+            OutputStream::Stdout => self.backend_log_path.lock().await.clone(),
+            OutputStream::Stderr => self.backend_stderr_log_path.lock().await.clone(),
+        }
+    }
+
+    /// Log files of the current (or most recent) backend run
+    async fn log_paths(&self) -> Option<LogPaths> {
+        Some(LogPaths {
+            stdout: self.backend_log_path.lock().await.clone()?,
+            stderr: self.backend_stderr_log_path.lock().await.clone()?,
+        })
+    }
+
+    async fn set_backend_state(&self, next: BackendState) {
+        let mut current = self.backend_state.lock().await;
+        if *current != next {
+            info!("Backend state: {:?} -> {:?}", *current, next);
+            match next {
+                // A reconnect keeps the original uptime
+                BackendState::Ready if *current != BackendState::Reconnecting => {
+                    *self.ready_since.lock().await = Some(std::time::Instant::now());
+                    *self.ready_at_ms.lock().await = Some(unix_time_ms());
+                }
+                BackendState::Stopped | BackendState::Crashed | BackendState::Failed => {
+                    *self.ready_since.lock().await = None;
+                    *self.ready_at_ms.lock().await = None;
+                }
+                _ => {}
+            }
+            *current = next;
+        }
+    }
 }
 
-#[tauri::command]
-async fn read_backend_log_chunk(
-    state: tauri::State<'_, Arc<AppState>>,
-    offset: usize,
-    max_bytes: Option<usize>,
-) -> Result<BackendLogChunk, String> {
-    let log_path = state.backend_log_path.lock().await.clone();
-    let Some(path) = log_path else {
-        return Ok(BackendLogChunk {
-            next_offset: offset,
-            text: String::new(),
-        });
-    };
+/// Milliseconds since the Unix epoch
+fn unix_time_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
 
-    let mut file = fs::File::open(&path)
-        .map_err(|e| format!("Failed to open backend log {:?}: {}", path, e))?;
-    let file_len = file
-        .metadata()
-        .map_err(|e| format!("Failed to read backend log metadata {:?}: {}", path, e))?
-        .len() as usize;
+/// Xorshift generator for timing jitter; fast and dependency-free, not
+/// meant for anything that has to be unpredictable
+struct JitterRng(u64);
 
-    let normalized_offset = offset.min(file_len);
-    file.seek(SeekFrom::Start(normalized_offset as u64))
-        .map_err(|e| format!("Failed to seek backend log {:?}: {}", path, e))?;
+impl JitterRng {
+    /// Seeded from the clock and PID so instances started together differ.
+    /// Tests use a fixed seed so delays are reproducible.
+    fn new() -> Self {
+        #[cfg(test)]
+        let seed = 0x9E37_79B9_7F4A_7C15;
+        #[cfg(not(test))]
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+            ^ (u64::from(std::process::id()) << 32);
+        // Xorshift never leaves an all-zero state
+        Self(seed | 1)
+    }
 
-    let limit = max_bytes.unwrap_or(64 * 1024).clamp(1024, 1024 * 1024);
-    let to_read = (file_len - normalized_offset).min(limit);
-    if to_read == 0 {
-        return Ok(BackendLogChunk {
-            next_offset: normalized_offset,
-            text: String::new(),
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// `base_ms` moved by up to ± `percent` %, uniformly
+    fn jitter(&mut self, base_ms: u64, percent: u64) -> u64 {
+        let spread = base_ms * percent / 100;
+        if spread == 0 {
+            return base_ms;
+        }
+        base_ms - spread + self.next_u64() % (2 * spread + 1)
+    }
+}
+
+/// A lifecycle event recorded in the event journal
+#[derive(Clone, serde::Serialize)]
+struct JournalEntry {
+    id: u64,
+    event: String,
+    payload: serde_json::Value,
+    timestamp_ms: u64,
+}
+
+/// Bounded journal of the most recent lifecycle events.
+///
+/// Ids start at 1 and increase by one for every recorded event, in the order
+/// the events were emitted. Entries are recorded before the Tauri event is
+/// emitted, so polling `get_events_since` never lags behind the push channel.
+/// Once `EVENT_JOURNAL_CAPACITY` is exceeded the oldest entries are dropped.
+///
+/// Periodic snapshots are emitted without being journaled, since they would
+/// soon push the lifecycle events out, so `get_events_since` cannot replay
+/// them. After a reload the frontend reads their current values instead:
+/// `backend-progress`, `backend-health-snapshot` and `backend-heartbeat`
+/// through `get_backend_info`, `metrics-updated` through `get_metrics`, and
+/// `backend-log-appended` through the log readers. `backend-port-wait` only
+/// matters while a start is waiting for its port and is not replayed.
+#[derive(Default)]
+struct EventJournal {
+    entries: VecDeque<JournalEntry>,
+    last_id: u64,
+}
+
+impl EventJournal {
+    fn record(&mut self, event: &str, payload: serde_json::Value) -> u64 {
+        self.last_id += 1;
+        self.entries.push_back(JournalEntry {
+            id: self.last_id,
+            event: event.to_string(),
+            payload,
+            timestamp_ms: unix_time_ms(),
         });
+        while self.entries.len() > EVENT_JOURNAL_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.last_id
     }
 
-    let mut buffer = vec![0u8; to_read];
-    let read = file
-        .read(&mut buffer)
-        .map_err(|e| format!("Failed to read backend log {:?}: {}", path, e))?;
-    buffer.truncate(read);
-    let text = String::from_utf8_lossy(&buffer).to_string();
+    fn since(&self, last_id: u64) -> EventsSince {
+        let missed = self
+            .entries
+            .front()
+            .map(|entry| entry.id > last_id.saturating_add(1))
+            .unwrap_or(false);
+        EventsSince {
+            events: self
+                .entries
+                .iter()
+                .filter(|entry| entry.id > last_id)
+                .cloned()
+                .collect(),
+            latest_id: self.last_id,
+            missed,
+        }
+    }
+}
 
-    Ok(BackendLogChunk {
-        next_offset: normalized_offset + read,
-        text,
-    })
+#[derive(serde::Serialize)]
+struct EventsSince {
+    events: Vec<JournalEntry>,
+    latest_id: u64,
+    /// True when some events after the requested id were already evicted
+    missed: bool,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Record a lifecycle event in the journal and emit it to the frontend
+async fn emit_backend_event<S: serde::Serialize + Clone>(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    event: &str,
+    payload: S,
+) {
+    let value = serde_json::to_value(payload.clone()).unwrap_or(serde_json::Value::Null);
+    state.event_journal.lock().await.record(event, value);
+    if let Err(e) = app.emit(event, payload) {
+        error!("Failed to emit {} event: {}", event, e);
+    }
+}
 
-    #[test]
-    fn test_is_dev_mode() {
-        // In debug builds, this should return true
-        #[cfg(debug_assertions)]
-        assert!(is_dev_mode());
+/// Length of the longest prefix of `bytes` that doesn't end in the middle of
+/// a UTF-8 sequence, so a split character is deferred to the next read
+fn utf8_complete_len(bytes: &[u8]) -> usize {
+    // A UTF-8 sequence is at most 4 bytes; look back for its lead byte
+    for back in 1..=bytes.len().min(4) {
+        let byte = bytes[bytes.len() - back];
+        if byte & 0b1100_0000 == 0b1000_0000 {
+            continue; // continuation byte
+        }
+        let needed = match byte {
+            b if b & 0b1000_0000 == 0 => 1,
+            b if b & 0b1110_0000 == 0b1100_0000 => 2,
+            b if b & 0b1111_0000 == 0b1110_0000 => 3,
+            b if b & 0b1111_1000 == 0b1111_0000 => 4,
+            _ => return bytes.len(), // invalid lead byte; let lossy decoding handle it
+        };
+        return if back < needed {
+            bytes.len() - back
+        } else {
+            bytes.len()
+        };
+    }
+    bytes.len()
+}
 
-        // In release builds, this should return false
-        #[cfg(not(debug_assertions))]
-        assert!(!is_dev_mode());
+/// Length of the prefix of `bytes` that doesn't end inside an ANSI escape
+/// sequence, so a sequence split across reads is stripped as a whole later
+fn ansi_complete_len(bytes: &[u8]) -> usize {
+    // Escape sequences in log output are short; only look at the tail
+    const MAX_SEQUENCE_LEN: usize = 32;
+    let tail_start = bytes.len().saturating_sub(MAX_SEQUENCE_LEN);
+    let Some(esc) = bytes[tail_start..].iter().rposition(|&b| b == 0x1b) else {
+        return bytes.len();
+    };
+    let esc = tail_start + esc;
+    let complete = match bytes.get(esc + 1) {
+        None => false,
+        // CSI: ESC [ params... final byte in 0x40..=0x7e
+        Some(b'[') => bytes[esc + 2..].iter().any(|b| (0x40..=0x7e).contains(b)),
+        // OSC: terminated by BEL or ESC \ (the latter would be a later ESC)
+        Some(b']') => bytes[esc + 2..].contains(&0x07),
+        Some(_) => true,
+    };
+    if complete {
+        bytes.len()
+    } else {
+        esc
+    }
+}
+
+/// Remove ANSI escape sequences (colors, cursor movement) from log text
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('\u{40}'..='\u{7e}').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\u{07}' {
+                        break;
+                    }
+                    if c == '\u{1b}' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Batch of log output pushed by the log stream
+#[derive(Clone, serde::Serialize)]
+struct LogAppended {
+    stream: OutputStream,
+    /// Increments by one per event so the frontend can detect gaps
+    seq: u64,
+    /// Byte offset of `text` in the log file
+    offset: usize,
+    next_offset: usize,
+    text: String,
+    /// Bytes skipped before `offset` because output outpaced the stream
+    skipped_bytes: usize,
+    /// The log was truncated, rotated or replaced since the previous event;
+    /// `offset` then refers to the new file
+    rotated: bool,
+}
+
+/// Tail a backend log, coalescing new output into one event per interval
+async fn run_log_stream(
+    app: tauri::AppHandle,
+    state: Arc<AppState>,
+    cancel: Arc<AtomicBool>,
+    stream: OutputStream,
+    interval_ms: u64,
+    mut offset: usize,
+    strip: bool,
+) {
+    let mut seq: u64 = 0;
+    // Identity of the file being tailed: its path and first bytes, the way
+    // `LineIndex` spots a replaced log
+    let mut tailed_path: Option<PathBuf> = None;
+    let mut head: Vec<u8> = Vec::new();
+    let mut rotated = false;
+    while !cancel.load(Ordering::SeqCst) {
+        sleep(Duration::from_millis(interval_ms)).await;
+        let Some(path) = state.log_path(stream).await else {
+            continue;
+        };
+        let Ok(mut file) = fs::File::open(&path) else {
+            continue;
+        };
+        let file_len = file.metadata().map(|m| m.len() as usize).unwrap_or(0);
+        let mut new_head = Vec::new();
+        if (&mut file)
+            .take(LOG_INDEX_HEAD_BYTES as u64)
+            .read_to_end(&mut new_head)
+            .is_err()
+        {
+            continue;
+        }
+        let shared = new_head.len().min(head.len());
+        let path_changed = tailed_path.as_ref().is_some_and(|tailed| *tailed != path);
+        if path_changed || file_len < offset || new_head[..shared] != head[..shared] {
+            // Truncated, rotated or replaced; start over from the beginning
+            offset = 0;
+            rotated = true;
+            head.clear();
+        }
+        tailed_path = Some(path);
+        if head.len() < new_head.len() {
+            head = new_head;
+        }
+        if file_len == offset {
+            continue;
+        }
+
+        let mut skipped_bytes = 0;
+        if file_len - offset > LOG_STREAM_MAX_BATCH_BYTES {
+            skipped_bytes = file_len - offset - LOG_STREAM_MAX_BATCH_BYTES;
+            offset += skipped_bytes;
+        }
+        if file.seek(SeekFrom::Start(offset as u64)).is_err() {
+            continue;
+        }
+        let mut buffer = vec![0u8; file_len - offset];
+        let Ok(read) = file.read(&mut buffer) else {
+            continue;
+        };
+        buffer.truncate(utf8_complete_len(&buffer[..read]));
+        if strip {
+            buffer.truncate(ansi_complete_len(&buffer));
+        }
+        if buffer.is_empty() && skipped_bytes == 0 {
+            continue;
+        }
+
+        seq += 1;
+        let text = String::from_utf8_lossy(&buffer);
+        let event = LogAppended {
+            stream,
+            seq,
+            offset,
+            next_offset: offset + buffer.len(),
+            text: if strip {
+                strip_ansi(&text)
+            } else {
+                text.to_string()
+            },
+            skipped_bytes,
+            rotated: std::mem::take(&mut rotated),
+        };
+        offset = event.next_offset;
+        if let Err(e) = app.emit("backend-log-appended", event) {
+            warn!("Failed to emit backend-log-appended event: {}", e);
+        }
+    }
+}
+
+/// Backend output written between spawn and readiness (or startup failure)
+#[derive(Clone, Default, serde::Serialize)]
+struct StartupLog {
+    /// Startup output on stdout
+    text: String,
+    /// Bytes dropped from the front because startup output exceeded
+    /// `STARTUP_LOG_MAX_BYTES`
+    truncated_bytes: u64,
+    /// Startup output on stderr, where uvicorn logs and tracebacks go
+    stderr_text: String,
+    stderr_truncated_bytes: u64,
+    /// False while the backend is still starting (the texts are then live)
+    complete: bool,
+    #[serde(skip)]
+    log_path: Option<PathBuf>,
+    #[serde(skip)]
+    log_start: u64,
+    #[serde(skip)]
+    stderr_log_path: Option<PathBuf>,
+    #[serde(skip)]
+    stderr_log_start: u64,
+}
+
+impl StartupLog {
+    /// Startup output of one stream written so far, keeping the newest bytes
+    fn read(&self, stream: OutputStream) -> (String, u64) {
+        let (path, log_start) = match stream {
+            OutputStream::Stdout => (&self.log_path, self.log_start),
+            OutputStream::Stderr => (&self.stderr_log_path, self.stderr_log_start),
+        };
+        let Some(path) = path else {
+            return (String::new(), 0);
+        };
+        let Ok(mut file) = fs::File::open(path) else {
+            return (String::new(), 0);
+        };
+        let file_len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let available = file_len.saturating_sub(log_start);
+        let truncated = available.saturating_sub(STARTUP_LOG_MAX_BYTES as u64);
+        if file.seek(SeekFrom::Start(log_start + truncated)).is_err() {
+            return (String::new(), 0);
+        }
+        let mut buffer = Vec::new();
+        if file
+            .take(available - truncated)
+            .read_to_end(&mut buffer)
+            .is_err()
+        {
+            return (String::new(), 0);
+        }
+        // Skip a character cut by truncation
+        let lead = if truncated > 0 {
+            buffer.iter().take_while(|b| (**b & 0xC0) == 0x80).count()
+        } else {
+            0
+        };
+        (
+            String::from_utf8_lossy(&buffer[lead..]).to_string(),
+            truncated + lead as u64,
+        )
+    }
+
+    /// Startup log with the texts read live
+    fn snapshot(&self) -> Self {
+        let (text, truncated_bytes) = self.read(OutputStream::Stdout);
+        let (stderr_text, stderr_truncated_bytes) = self.read(OutputStream::Stderr);
+        Self {
+            text,
+            truncated_bytes,
+            stderr_text,
+            stderr_truncated_bytes,
+            ..self.clone()
+        }
+    }
+
+    /// Freeze the buffer once startup has finished
+    fn capture(&mut self) {
+        *self = Self {
+            complete: true,
+            ..self.snapshot()
+        };
+    }
+}
+
+/// Backend output stream, each logged to its own file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum OutputStream {
+    #[default]
+    Stdout,
+    Stderr,
+}
+
+/// Log files of one backend run
+#[derive(Clone, Debug)]
+struct LogPaths {
+    stdout: PathBuf,
+    stderr: PathBuf,
+}
+
+#[derive(serde::Serialize)]
+struct BackendLogLines {
+    start_line: usize,
+    lines: Vec<String>,
+    /// Lines in the whole log, including a last line still being written
+    total_lines: usize,
+}
+
+/// A `search_backend_log` hit
+#[derive(Debug, serde::Serialize)]
+struct LogMatch {
+    /// Zero-based, as `start_line` of `read_backend_log_lines`
+    line_number: usize,
+    /// Byte offset of the line start, for `read_backend_log_chunk`
+    offset: u64,
+    line: String,
+    before: Vec<String>,
+    after: Vec<String>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct LogSearchResult {
+    matches: Vec<LogMatch>,
+    /// More lines matched than were returned
+    truncated: bool,
+}
+
+/// Scan `reader` line by line for `pattern`, matching the ANSI-stripped text.
+///
+/// Only `context` preceding lines are held in memory besides the matches.
+/// After `max_matches` hits the scan goes on just far enough to fill their
+/// trailing context and tell whether anything else matched.
+fn search_log(
+    mut reader: impl std::io::BufRead,
+    pattern: &regex::Regex,
+    context: usize,
+    max_matches: usize,
+) -> std::io::Result<LogSearchResult> {
+    let mut matches: Vec<LogMatch> = Vec::new();
+    // Index of the first match still collecting trailing context
+    let mut pending = 0;
+    let mut before: VecDeque<String> = VecDeque::with_capacity(context + 1);
+    let mut truncated = false;
+    let mut bytes = Vec::new();
+    let mut offset = 0u64;
+    let mut line_number = 0;
+    loop {
+        bytes.clear();
+        let read = reader.read_until(b'\n', &mut bytes)?;
+        if read == 0 {
+            break;
+        }
+        let text = String::from_utf8_lossy(&bytes);
+        let line = strip_ansi(text.trim_end_matches(['\n', '\r']));
+
+        for hit in &mut matches[pending..] {
+            hit.after.push(line.clone());
+        }
+        while pending < matches.len() && matches[pending].after.len() >= context {
+            pending += 1;
+        }
+
+        if pattern.is_match(&line) {
+            if matches.len() < max_matches {
+                matches.push(LogMatch {
+                    line_number,
+                    offset,
+                    line: line.clone(),
+                    before: before.iter().cloned().collect(),
+                    after: Vec::new(),
+                });
+                if context == 0 {
+                    pending = matches.len();
+                }
+            } else {
+                truncated = true;
+            }
+        }
+        if truncated && pending == matches.len() {
+            break;
+        }
+
+        if context > 0 {
+            if before.len() == context {
+                before.pop_front();
+            }
+            before.push_back(line);
+        }
+        offset += read as u64;
+        line_number += 1;
+    }
+    Ok(LogSearchResult { matches, truncated })
+}
+
+#[derive(serde::Serialize)]
+struct BackendLogChunk {
+    next_offset: usize,
+    text: String,
+    /// Read limit actually used after clamping `max_bytes` (and the
+    /// low-memory cap), so callers can page without guessing
+    applied_max_bytes: usize,
+    /// `offset` was past the end of the log, which was truncated or
+    /// rotated since; `text` then starts at the top of the new file
+    rotated: bool,
+}
+
+#[derive(serde::Serialize)]
+struct BackendLogEntries {
+    next_offset: usize,
+    lines: Vec<LogLine>,
+    applied_max_bytes: usize,
+    /// As in `BackendLogChunk`
+    rotated: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LogLevel {
+    Debug,
+    Info,
+    Warning,
+    Error,
+    Critical,
+    Unknown,
+}
+
+impl LogLevel {
+    fn parse(word: &str) -> Option<Self> {
+        match word.to_ascii_uppercase().as_str() {
+            "DEBUG" | "TRACE" => Some(Self::Debug),
+            "INFO" => Some(Self::Info),
+            "WARN" | "WARNING" => Some(Self::Warning),
+            "ERROR" => Some(Self::Error),
+            "CRITICAL" | "FATAL" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+}
+
+/// One backend log line split into its parts
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+struct LogLine {
+    level: LogLevel,
+    timestamp: Option<String>,
+    /// The text after the level, without ANSI codes
+    message: String,
+    /// The line as written
+    raw: String,
+}
+
+impl LogLine {
+    /// Recognizes uvicorn's `INFO:     message`, Python's default
+    /// `INFO:name:message` and our backend's
+    /// `2024-01-01 12:00:00,000 - name - INFO - message`
+    fn parse(raw: &str) -> Self {
+        let text = strip_ansi(raw);
+        let parsed = Self::parse_level_prefix(&text).or_else(|| Self::parse_dashed(&text));
+        let (level, timestamp, message) = parsed.unwrap_or((LogLevel::Unknown, None, text.clone()));
+        Self {
+            level,
+            timestamp,
+            message,
+            raw: raw.to_string(),
+        }
+    }
+
+    /// `LEVEL:` at the start of the line
+    fn parse_level_prefix(text: &str) -> Option<(LogLevel, Option<String>, String)> {
+        let (word, rest) = text.split_once(':')?;
+        let level = LogLevel::parse(word)?;
+        Some((level, None, rest.trim_start().to_string()))
+    }
+
+    /// `timestamp - name - LEVEL - message`
+    fn parse_dashed(text: &str) -> Option<(LogLevel, Option<String>, String)> {
+        let parts: Vec<&str> = text.splitn(4, " - ").collect();
+        let [timestamp, _name, level, message] = parts[..] else {
+            return None;
+        };
+        if !timestamp.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        let level = LogLevel::parse(level.trim())?;
+        Some((level, Some(timestamp.to_string()), message.to_string()))
+    }
+}
+
+/// Check that a directory can be created and written to by creating and
+/// removing a marker file (catches read-only and full volumes)
+fn probe_dir_writable(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("cannot create directory: {}", e))?;
+    let marker = dir.join(format!(".alproj-write-test-{}", std::process::id()));
+    let result = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&marker)
+        .and_then(|mut file| std::io::Write::write_all(&mut file, b"ok"))
+        .map_err(|e| format!("not writable: {}", e));
+    let _ = fs::remove_file(&marker);
+    result
+}
+
+/// Directory for the backend logs: the first writable of the app log dir,
+/// the app data dir and the temp dir
+fn resolve_backend_log_dir(app: &tauri::AppHandle) -> PathBuf {
+    let mut candidates = Vec::new();
+    match app.path().app_log_dir() {
+        Ok(log_dir) => candidates.push(("app log dir", log_dir)),
+        Err(e) => warn!("Skipping app log dir for backend log: {}", e),
+    }
+    match app.path().app_data_dir() {
+        Ok(data_dir) => candidates.push(("app data dir", data_dir.join("logs"))),
+        Err(e) => warn!("Skipping app data dir for backend log: {}", e),
+    }
+    let temp_dir = std::env::temp_dir().join("alproj-gui");
+
+    for (label, dir) in candidates {
+        match probe_dir_writable(&dir) {
+            Ok(()) => {
+                info!("Using {} for backend log: {:?}", label, dir);
+                return dir;
+            }
+            Err(reason) => warn!("Skipping {} {:?} for backend log: {}", label, dir, reason),
+        }
+    }
+
+    if let Err(reason) = probe_dir_writable(&temp_dir) {
+        warn!(
+            "Temp dir {:?} for backend log is also unusable: {}",
+            temp_dir, reason
+        );
+    } else {
+        info!("Using temp dir for backend log: {:?}", temp_dir);
+    }
+    temp_dir
+}
+
+/// Open a backend log file for appending.
+///
+/// stdout and stderr each get their own file, so tracebacks don't end up
+/// interleaved with regular output.
+fn open_backend_log(log_path: &Path) -> Result<fs::File, String> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)
+        .map_err(|e| format!("Failed to open backend log file {:?}: {}", log_path, e))
+}
+
+/// Open a backend log, or a new timestamped file next to it when that
+/// fails (on Windows typically a sharing violation: another process
+/// holds the file without FILE_SHARE_WRITE).
+///
+/// Returns the path actually used and, after a fallback, why the usual log
+/// was skipped. Fallback files beyond the newest `keep` are removed first.
+fn open_backend_log_or_fallback(
+    log_path: PathBuf,
+    keep: u32,
+) -> Result<(PathBuf, fs::File, Option<String>), String> {
+    remove_old_fallback_logs(&log_path, keep);
+    let error = match open_backend_log(&log_path) {
+        Ok(log) => return Ok((log_path, log, None)),
+        Err(e) => e,
+    };
+    let stem = log_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "backend".to_string());
+    let fallback = log_path.with_file_name(format!("{}-{}.log", stem, unix_time_ms()));
+    warn!("{}; logging to {:?} instead", error, fallback);
+    let log = open_backend_log(&fallback)
+        .map_err(|fallback_error| format!("{} (fallback: {})", error, fallback_error))?;
+    Ok((fallback, log, Some(error)))
+}
+
+/// Delete all but the newest `keep` timestamped fallback copies
+/// (`<stem>-<unix ms>.log`) of the log at `log_path`
+fn remove_old_fallback_logs(log_path: &Path, keep: u32) {
+    let (Some(dir), Some(stem)) = (log_path.parent(), log_path.file_stem()) else {
+        return;
+    };
+    let prefix = format!("{}-", stem.to_string_lossy());
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut fallbacks: Vec<(u64, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let stamp = name.strip_prefix(&prefix)?.strip_suffix(".log")?;
+            Some((stamp.parse().ok()?, entry.path()))
+        })
+        .collect();
+    fallbacks.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+    for (_, path) in fallbacks.into_iter().skip(keep as usize) {
+        if let Err(e) = fs::remove_file(&path) {
+            warn!("Failed to remove old fallback log {:?}: {}", path, e);
+        }
+    }
+}
+
+/// Read at most the last `max_bytes` of a file; the first line is dropped
+/// when the read starts mid-file since it is most likely partial
+fn read_file_tail(path: &Path, max_bytes: u64) -> std::io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.take(max_bytes).read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes).to_string();
+    if start == 0 {
+        return Ok(text);
+    }
+    Ok(match text.split_once('\n') {
+        Some((_, rest)) => rest.to_string(),
+        None => text,
+    })
+}
+
+/// The last `max_lines` lines of `text`, dropping whole lines from the top
+/// until the rest fits in `max_chars` characters. Only a single last line
+/// longer than the budget is cut, keeping its end.
+fn tail_lines(text: &str, max_lines: usize, max_chars: usize) -> String {
+    let mut kept = Vec::new();
+    let mut chars = 0;
+    for line in text.lines().rev().take(max_lines) {
+        // Joining newline included
+        let cost = line.chars().count() + usize::from(!kept.is_empty());
+        if chars + cost > max_chars {
+            if kept.is_empty() {
+                let skip = line.chars().count() - max_chars;
+                let cut = line.char_indices().nth(skip).map_or(0, |(i, _)| i);
+                kept.push(&line[cut..]);
+            }
+            break;
+        }
+        chars += cost;
+        kept.push(line);
+    }
+    kept.reverse();
+    kept.join("\n")
+}
+
+fn format_log_tail(log_path: &Path, max_lines: usize) -> String {
+    let text = match read_file_tail(log_path, LOG_TAIL_MAX_BYTES) {
+        Ok(text) => text,
+        Err(e) => {
+            return format!("Backend log read failed: {} ({})", e, log_path.display());
+        }
+    };
+
+    const MAX_CHARS: usize = 4000;
+    let tail = tail_lines(&strip_ansi(&text), max_lines, MAX_CHARS);
+
+    format!(
+        "Backend log: {}\n--- log tail ---\n{}\n----------------",
+        log_path.display(),
+        tail
+    )
+}
+
+async fn read_backend_log_tail(
+    state: &AppState,
+    stream: OutputStream,
+    max_lines: usize,
+) -> Option<String> {
+    let log_path = state.log_path(stream).await;
+    log_path.map(|path| format_log_tail(&path, max_lines))
+}
+
+/// Log tail for error messages: stderr, where tracebacks and uvicorn's
+/// errors go, or stdout while stderr is still empty
+async fn read_backend_error_tail(state: &AppState, max_lines: usize) -> Option<String> {
+    let stderr_written = state
+        .log_path(OutputStream::Stderr)
+        .await
+        .and_then(|path| fs::metadata(path).ok())
+        .is_some_and(|meta| meta.len() > 0);
+    let stream = if stderr_written {
+        OutputStream::Stderr
+    } else {
+        OutputStream::Stdout
+    };
+    read_backend_log_tail(state, stream, max_lines).await
+}
+
+/// Whether a process with this PID is still running
+fn process_exists(pid: u32) -> bool {
+    let mut sys = System::new();
+    let sys_pid = Pid::from_u32(pid);
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[sys_pid]), true);
+    sys.process(sys_pid).is_some()
+}
+
+/// PID of a backend that has no exit status to wait on (Tauri sidecar or
+/// attached) and is no longer running
+async fn sidecar_process_gone(state: &AppState) -> Option<u32> {
+    let pid = match state.sidecars.lock().await.get(MAIN_SERVICE) {
+        Some(ProcessHandle::TauriChild(child)) => child.pid(),
+        Some(ProcessHandle::Attached { pid }) => *pid,
+        _ => return None,
+    };
+    (!process_exists(pid)).then_some(pid)
+}
+
+/// Exit status of the spawned backend, if it has exited
+async fn sidecar_exit_status(state: &AppState) -> Option<std::process::ExitStatus> {
+    let mut sidecars = state.sidecars.lock().await;
+    match sidecars.get_mut(MAIN_SERVICE) {
+        Some(ProcessHandle::StdChild(child)) => child.try_wait().ok().flatten(),
+        _ => None,
+    }
+}
+
+/// Why the backend could not bind its address
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ErrorCategory {
+    AddressInUse,
+    PermissionDenied,
+    AddressNotAvailable,
+}
+
+impl ErrorCategory {
+    fn from_io_kind(kind: std::io::ErrorKind) -> Option<Self> {
+        match kind {
+            std::io::ErrorKind::AddrInUse => Some(Self::AddressInUse),
+            std::io::ErrorKind::PermissionDenied => Some(Self::PermissionDenied),
+            std::io::ErrorKind::AddrNotAvailable => Some(Self::AddressNotAvailable),
+            _ => None,
+        }
+    }
+
+    /// Recognize a bind failure in backend output (uvicorn prints the OS
+    /// error, e.g. `[Errno 98] ... address already in use`)
+    fn from_log(text: &str) -> Option<Self> {
+        let text = text.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|needle| text.contains(needle));
+        if has(&[
+            "address already in use",
+            "errno 98]",
+            "errno 48]",
+            "errno 10048]",
+        ]) {
+            Some(Self::AddressInUse)
+        } else if has(&[
+            "cannot assign requested address",
+            "can't assign requested address",
+            "errno 99]",
+            "errno 49]",
+            "errno 10049]",
+        ]) {
+            Some(Self::AddressNotAvailable)
+        } else if has(&["error while attempting to bind"])
+            && has(&["permission denied", "errno 13]", "errno 10013]"])
+        {
+            Some(Self::PermissionDenied)
+        } else {
+            None
+        }
+    }
+
+    fn message(self, host: &str, port: u16) -> String {
+        match self {
+            Self::AddressInUse => match find_port_owner(port) {
+                Some(owner) => format!("Port {} is already in use by {}", port, owner),
+                None => format!("Port {} is already in use by another process", port),
+            },
+            Self::PermissionDenied => format!(
+                "Binding {}:{} was denied: the port requires elevated privileges or is blocked by a sandbox; choose a port above 1024 with {}",
+                host, port, ENV_BACKEND_PORT
+            ),
+            Self::AddressNotAvailable => format!(
+                "Host {} is not available on this machine (no network interface has that address)",
+                host
+            ),
+        }
+    }
+}
+
+async fn check_sidecar_exited(state: &Arc<AppState>) -> Option<String> {
+    let exit = {
+        let mut sidecars = state.sidecars.lock().await;
+        match sidecars.get_mut(MAIN_SERVICE) {
+            Some(ProcessHandle::StdChild(child)) => match child.try_wait() {
+                Ok(Some(status)) => Some(status),
+                Ok(None) => None,
+                Err(e) => {
+                    return Some(format!("Failed to query backend process status: {}", e));
+                }
+            },
+            Some(ProcessHandle::Attached { pid }) => {
+                // No exit status for a process we did not spawn, only whether it is gone
+                if !process_exists(*pid) {
+                    return Some(format!("Attached backend process {} has exited", pid));
+                }
+                None
+            }
+            _ => None,
+        }
+    };
+
+    if let Some(status) = exit {
+        let code_text = match status.code() {
+            Some(code) => format!("exit code {}", code),
+            None => "terminated by signal".to_string(),
+        };
+        if let Some(log_tail) = read_backend_error_tail(state, 80).await {
+            let port = state.backend_port.load(Ordering::SeqCst);
+            if let Some(category) = ErrorCategory::from_log(&log_tail) {
+                return Some(format!(
+                    "{} ({})\n{}",
+                    category.message(BACKEND_HOST, port),
+                    code_text,
+                    log_tail
+                ));
+            }
+            return Some(format!(
+                "Backend process exited before ready ({})\n{}",
+                code_text, log_tail
+            ));
+        }
+        return Some(format!(
+            "Backend process exited before ready ({})",
+            code_text
+        ));
+    }
+
+    None
+}
+
+/// Check if we're running in development mode
+fn is_dev_mode() -> bool {
+    cfg!(debug_assertions)
+}
+
+/// Common uv installation paths, most likely first
+fn uv_candidates() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    #[cfg(windows)]
+    {
+        // `HOME` is normally unset on Windows
+        if let Some(profile) = env_var_non_empty("USERPROFILE") {
+            let profile = PathBuf::from(profile);
+            candidates.push(profile.join(".local").join("bin").join("uv.exe"));
+            candidates.push(profile.join(".cargo").join("bin").join("uv.exe"));
+        }
+        if let Some(local) = env_var_non_empty("LOCALAPPDATA") {
+            let local = PathBuf::from(local);
+            candidates.push(local.join("Programs").join("uv").join("uv.exe"));
+            candidates.push(
+                local
+                    .join("Microsoft")
+                    .join("WinGet")
+                    .join("Links")
+                    .join("uv.exe"),
+            );
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        if let Some(home) = env_var_non_empty("HOME") {
+            let home = PathBuf::from(home);
+            candidates.push(home.join(".local").join("bin").join("uv"));
+            candidates.push(home.join(".cargo").join("bin").join("uv"));
+        }
+        candidates.push(PathBuf::from("/usr/local/bin/uv"));
+        candidates.push(PathBuf::from("/opt/homebrew/bin/uv"));
+    }
+    candidates
+}
+
+/// Run `path args...`, returning its output, or why the executable is
+/// unusable (not executable, wrong architecture, hangs, ...). Blocks for
+/// up to `UV_VERSION_TIMEOUT_MS`, so async callers go through `spawn_blocking`.
+fn check_command_runs(path: &str, args: &[&str]) -> Result<String, String> {
+    let shown = args.join(" ");
+    let mut command = Command::new(path);
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    #[cfg(windows)]
+    command.creation_flags(CREATE_NO_WINDOW);
+    let mut child = command.spawn().map_err(|e| e.to_string())?;
+
+    let start = std::time::Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if start.elapsed() < Duration::from_millis(UV_VERSION_TIMEOUT_MS) => {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "`{}` did not finish within {} ms",
+                    shown, UV_VERSION_TIMEOUT_MS
+                ));
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+    if !status.success() {
+        return Err(format!("`{}` failed ({})", shown, status));
+    }
+    let mut output = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        let _ = stdout.read_to_string(&mut output);
+    }
+    Ok(output.trim().to_string())
+}
+
+/// Find uv executable in common installation locations
+/// Tauri doesn't inherit the shell PATH, so we need to check common paths.
+///
+/// With `verify`, a candidate is only accepted once `uv --version` succeeds;
+/// the error then lists every path tried and why it was rejected.
+fn find_uv_path(verify: bool) -> Result<String, String> {
+    // Plain "uv" last, to let PATH lookup try
+    let mut candidates = uv_candidates()
+        .into_iter()
+        .filter(|path| path.exists())
+        .map(|path| path.to_string_lossy().to_string())
+        .chain(std::iter::once("uv".to_string()));
+    if !verify {
+        // The chain always ends with "uv"
+        return Ok(candidates.next().unwrap_or_default());
+    }
+
+    let mut tried = Vec::new();
+    for path in candidates {
+        match check_command_runs(&path, &["--version"]) {
+            Ok(version) => {
+                info!("Using {} ({})", path, version);
+                return Ok(path);
+            }
+            Err(e) => tried.push(format!("  {}: {}", path, e)),
+        }
+    }
+    let not_found = uv_candidates()
+        .into_iter()
+        .filter(|path| !path.exists())
+        .map(|path| format!("  {}: not found", path.display()));
+    Err(format!(
+        "Could not find a working uv. Please ensure uv is installed. Tried:\n{}",
+        tried
+            .into_iter()
+            .chain(not_found)
+            .collect::<Vec<_>>()
+            .join("\n")
+    ))
+}
+
+/// First interpreter of `SYSTEM_PYTHON_CANDIDATES` that runs and can
+/// import uvicorn
+fn find_system_python() -> Result<String, String> {
+    let mut tried = Vec::new();
+    for candidate in SYSTEM_PYTHON_CANDIDATES {
+        match check_command_runs(
+            candidate,
+            &["-c", "import sys, uvicorn; print(sys.version.split()[0])"],
+        ) {
+            Ok(version) => {
+                info!("Using system {} (Python {})", candidate, version);
+                return Ok(candidate.to_string());
+            }
+            Err(e) => tried.push(format!("{}: {}", candidate, e)),
+        }
+    }
+    Err(format!(
+        "no Python with uvicorn on PATH ({})",
+        tried.join("; ")
+    ))
+}
+
+/// The dev-mode backend directory: `ALPROJ_BACKEND_DIR` when set, otherwise
+/// `backend` in the checkout that contains `src-tauri`
+fn get_dev_backend_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    if let Some(dir) = env_var_non_empty(ENV_BACKEND_DIR) {
+        let dir = PathBuf::from(dir);
+        if !dir.is_absolute() {
+            return Err(format!(
+                "{} must be an absolute path: {:?}",
+                ENV_BACKEND_DIR, dir
+            ));
+        }
+        return Ok(dir);
+    }
+
+    let src_tauri_dir = app
+        .path()
+        .resource_dir()
+        .map_err(|e| format!("Failed to get resource dir: {}", e))?
+        .parent()
+        .ok_or("Failed to get parent dir")?
+        .parent()
+        .ok_or("Failed to get src-tauri dir")?
+        .to_path_buf();
+
+    src_tauri_dir
+        .parent()
+        .ok_or_else(|| "Failed to get project root".to_string())
+        .map(|p| p.join("backend"))
+}
+
+fn find_dev_python(backend_dir: &Path) -> Option<PathBuf> {
+    #[cfg(windows)]
+    let candidates = [
+        backend_dir.join(".venv").join("Scripts").join("python.exe"),
+        backend_dir
+            .join(".venv")
+            .join("Scripts")
+            .join("python3.exe"),
+    ];
+    #[cfg(not(windows))]
+    let candidates = [
+        backend_dir.join(".venv").join("bin").join("python"),
+        backend_dir.join(".venv").join("bin").join("python3"),
+    ];
+
+    candidates.into_iter().find(|p| p.exists())
+}
+
+/// `--port N` / `--port=N` value from a command line, if any
+fn cmd_port_arg(args: &[String]) -> Option<u16> {
+    args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == "--port" {
+            args.get(i + 1)?.parse().ok()
+        } else {
+            arg.strip_prefix("--port=")?.parse().ok()
+        }
+    })
+}
+
+/// Candidate source files for a uvicorn `module:attribute` target, relative
+/// to the backend dir (`a.b:app` -> `a/b.py` or `a/b/__init__.py`)
+fn uvicorn_module_files(target: &str) -> Result<[PathBuf; 2], String> {
+    let (module, attribute) = target
+        .split_once(':')
+        .ok_or_else(|| format!("expected module:attribute, got {:?}", target))?;
+    let is_identifier = |part: &str| {
+        !part.is_empty()
+            && !part.starts_with(|c: char| c.is_ascii_digit())
+            && part.chars().all(|c| c.is_alphanumeric() || c == '_')
+    };
+    if !module.split('.').all(is_identifier) || attribute.trim().is_empty() {
+        return Err(format!("expected module:attribute, got {:?}", target));
+    }
+    let module_path = module.split('.').collect::<PathBuf>();
+    Ok([
+        module_path.with_extension("py"),
+        module_path.join("__init__.py"),
+    ])
+}
+
+/// Check that the module named by the uvicorn target exists under the
+/// backend dir, so a wrong layout fails fast instead of timing out
+fn validate_uvicorn_app(backend_dir: &Path, target: &str) -> Result<(), String> {
+    let files = uvicorn_module_files(target)
+        .map_err(|e| format!("Invalid {} target: {}", ENV_UVICORN_APP, e))?;
+    if files.iter().any(|file| backend_dir.join(file).is_file()) {
+        return Ok(());
+    }
+    Err(format!(
+        "uvicorn app {:?} not found: neither {:?} nor {:?} exists in {:?} (set {} to match the backend layout)",
+        target, files[0], files[1], backend_dir, ENV_UVICORN_APP
+    ))
+}
+
+fn is_backend_process_for_dir(
+    process: &sysinfo::Process,
+    backend_dir: &Path,
+    app_target: &str,
+    ports: &RangeInclusive<u16>,
+) -> bool {
+    let args = process
+        .cmd()
+        .iter()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    let cmd = args.join(" ");
+    if cmd.is_empty() {
+        return false;
+    }
+
+    let looks_like_uvicorn = cmd.contains("uvicorn")
+        && cmd.contains(app_target)
+        && cmd_port_arg(&args).is_some_and(|port| ports.contains(&port));
+    if !looks_like_uvicorn {
+        return false;
+    }
+
+    if let Some(cwd) = process.cwd() {
+        if cwd.starts_with(backend_dir) {
+            return true;
+        }
+    }
+
+    if let Some(exe) = process.exe() {
+        if exe.starts_with(backend_dir) {
+            return true;
+        }
+    }
+
+    cmd.contains(backend_dir.to_string_lossy().as_ref())
+}
+
+fn cleanup_stale_backend_processes(
+    backend_dir: &Path,
+    app_target: &str,
+    ports: &RangeInclusive<u16>,
+) -> usize {
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+
+    let current_pid = std::process::id();
+    let stale_pids = sys
+        .processes()
+        .iter()
+        .filter_map(|(pid, process)| {
+            let pid_u32 = pid.as_u32();
+            if pid_u32 == current_pid {
+                return None;
+            }
+            if is_backend_process_for_dir(process, backend_dir, app_target, ports) {
+                return Some(pid_u32);
+            }
+            None
+        })
+        .collect::<Vec<_>>();
+
+    for pid in &stale_pids {
+        warn!("Killing stale backend process PID {}", pid);
+        kill_process_tree(*pid);
+        if let Some(process) = sys.process(Pid::from_u32(*pid)) {
+            process.kill();
+        }
+    }
+
+    stale_pids.len()
+}
+
+/// Whether `process` is one of our backends: the bundled sidecar binary, or
+/// uvicorn serving `app_target`
+fn is_backend_process(process: &sysinfo::Process, app_target: &str) -> bool {
+    // `name` is truncated to 15 characters on Linux, the executable path is not
+    let exe_name = process
+        .exe()
+        .and_then(|exe| exe.file_name())
+        .map(|name| name.to_string_lossy().to_string());
+    let name = exe_name.unwrap_or_else(|| process.name().to_string_lossy().to_string());
+    if get_sidecar_binary_name().is_ok_and(|binary| name.starts_with(&binary)) {
+        return true;
+    }
+    let cmd = process
+        .cmd()
+        .iter()
+        .map(|arg| arg.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ");
+    cmd.contains("uvicorn") && cmd.contains(app_target)
+}
+
+/// Kill backends orphaned by a crashed or force-quit GUI that still listen
+/// on one of `ports`, so the new backend can bind. Listeners that are not
+/// our backend are left alone. Returns how many process trees were killed.
+fn reap_orphaned_backends(ports: &RangeInclusive<u16>, app_target: &str) -> usize {
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    let current_pid = std::process::id();
+
+    let mut reaped = Vec::new();
+    for port in ports.clone() {
+        let Some(listener) = find_listening_pid(port) else {
+            continue;
+        };
+        let Some(process) = sys.process(Pid::from_u32(listener)) else {
+            continue;
+        };
+        if listener == current_pid || !is_backend_process(process, app_target) {
+            continue;
+        }
+        // Kill from the uvicorn reloader down when the listener is its worker
+        let root = process
+            .parent()
+            .and_then(|parent| sys.process(parent))
+            .filter(|parent| {
+                parent.pid().as_u32() != current_pid && is_backend_process(parent, app_target)
+            })
+            .map_or(listener, |parent| parent.pid().as_u32());
+        if reaped.contains(&root) {
+            continue;
+        }
+        warn!(
+            "Killing orphaned backend PID {} ({}) listening on port {}",
+            root,
+            sys.process(Pid::from_u32(root))
+                .map(|p| p.name().to_string_lossy().to_string())
+                .unwrap_or_default(),
+            port
+        );
+        kill_process_tree(root);
+        if let Some(process) = sys.process(Pid::from_u32(root)) {
+            process.kill();
+        }
+        reaped.push(root);
+    }
+    reaped.len()
+}
+
+/// Target triples we ship a sidecar for
+const SIDECAR_TARGET_TRIPLES: &[&str] = &[
+    "aarch64-apple-darwin",
+    "x86_64-apple-darwin",
+    "aarch64-unknown-linux-gnu",
+    "x86_64-unknown-linux-gnu",
+    "x86_64-pc-windows-msvc",
+    "aarch64-pc-windows-msvc",
+];
+
+/// ABI part of this build's target triple
+const TARGET_ENV: &str = if cfg!(target_env = "musl") {
+    "musl"
+} else if cfg!(target_env = "msvc") {
+    "msvc"
+} else {
+    "gnu"
+};
+
+/// Rust target triple for an architecture, OS and ABI as named by
+/// `std::env::consts` and `target_env`
+fn target_triple(arch: &str, os: &str, env: &str) -> String {
+    match os {
+        "macos" => format!("{}-apple-darwin", arch),
+        "windows" => format!("{}-pc-windows-{}", arch, env),
+        "linux" => format!("{}-unknown-linux-{}", arch, env),
+        other => format!("{}-unknown-{}", arch, other),
+    }
+}
+
+/// Target triple of this build, if a sidecar is built for it
+fn sidecar_target_triple() -> Result<&'static str, String> {
+    let triple = target_triple(std::env::consts::ARCH, std::env::consts::OS, TARGET_ENV);
+    SIDECAR_TARGET_TRIPLES
+        .iter()
+        .copied()
+        .find(|supported| *supported == triple)
+        .ok_or_else(|| format!("No backend sidecar is built for this platform ({})", triple))
+}
+
+fn sidecar_dir_name(triple: &str) -> String {
+    format!("sidecar-{}", triple)
+}
+
+fn sidecar_binary_name(triple: &str) -> String {
+    let suffix = if triple.contains("-windows-") {
+        ".exe"
+    } else {
+        ""
+    };
+    format!("backend-sidecar-{}{}", triple, suffix)
+}
+
+/// Get the platform-specific sidecar directory name
+fn get_sidecar_dir_name() -> Result<String, String> {
+    sidecar_target_triple().map(sidecar_dir_name)
+}
+
+/// Get the platform-specific sidecar binary name
+fn get_sidecar_binary_name() -> Result<String, String> {
+    sidecar_target_triple().map(sidecar_binary_name)
+}
+
+/// Resource roots that may hold the bundled sidecar
+fn sidecar_resource_roots(app: &tauri::AppHandle) -> Result<Vec<PathBuf>, String> {
+    let resource_dir = app
+        .path()
+        .resource_dir()
+        .map_err(|e| format!("Failed to get resource dir: {}", e))?;
+
+    // Flatpak can place resources under /app/lib/<name>, so probe multiple roots.
+    #[cfg(target_os = "linux")]
+    let resource_roots = {
+        let mut roots = vec![resource_dir];
+        roots.push(PathBuf::from("/app/lib/alproj-gui"));
+        roots.push(PathBuf::from("/app/lib/com.alproj.gui"));
+        roots
+    };
+    #[cfg(not(target_os = "linux"))]
+    let resource_roots = vec![resource_dir];
+
+    Ok(resource_roots)
+}
+
+#[derive(serde::Deserialize)]
+struct BundleManifest {
+    files: Vec<BundleManifestEntry>,
+}
+
+#[derive(serde::Deserialize)]
+struct BundleManifestEntry {
+    /// Path relative to the bundle dir, `/`-separated
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+/// Result of checking the sidecar bundle against its manifest
+#[derive(Clone, serde::Serialize)]
+struct BundleReport {
+    bundle_dir: PathBuf,
+    checked: usize,
+    missing: Vec<String>,
+    /// Files whose size or hash differ from the manifest
+    corrupted: Vec<String>,
+    ok: bool,
+}
+
+fn sha256_file(path: &Path) -> std::io::Result<String> {
+    use sha2::Digest;
+    let mut file = fs::File::open(path)?;
+    let mut hasher = sha2::Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Check every file listed in the bundle manifest (blocking; hashes the
+/// whole bundle)
+fn verify_bundle(bundle_dir: &Path) -> Result<BundleReport, String> {
+    let manifest_path = bundle_dir.join(BUNDLE_MANIFEST_FILE_NAME);
+    let manifest = fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Failed to read bundle manifest {:?}: {}", manifest_path, e))?;
+    let manifest: BundleManifest = serde_json::from_str(&manifest)
+        .map_err(|e| format!("Invalid bundle manifest {:?}: {}", manifest_path, e))?;
+
+    let mut missing = Vec::new();
+    let mut corrupted = Vec::new();
+    for entry in &manifest.files {
+        let path = bundle_dir.join(entry.path.split('/').collect::<PathBuf>());
+        let Ok(meta) = fs::metadata(&path) else {
+            missing.push(entry.path.clone());
+            continue;
+        };
+        let intact = meta.len() == entry.size
+            && sha256_file(&path).is_ok_and(|hash| hash.eq_ignore_ascii_case(&entry.sha256));
+        if !intact {
+            corrupted.push(entry.path.clone());
+        }
+    }
+
+    Ok(BundleReport {
+        bundle_dir: bundle_dir.to_path_buf(),
+        checked: manifest.files.len(),
+        ok: missing.is_empty() && corrupted.is_empty(),
+        missing,
+        corrupted,
+    })
+}
+
+/// Compare the sidecar binary's SHA-256 with the bundled `checksum.txt`
+/// (blocking; hashes the binary). Bundles built without a checksum file
+/// are let through with a warning.
+fn verify_sidecar_checksum(sidecar_dir: &Path, sidecar_path: &Path) -> Result<(), String> {
+    let checksum_path = sidecar_dir.join(SIDECAR_CHECKSUM_FILE_NAME);
+    let expected = match fs::read_to_string(&checksum_path) {
+        Ok(text) => text
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            warn!(
+                "No {} next to the sidecar; launching it unverified",
+                SIDECAR_CHECKSUM_FILE_NAME
+            );
+            return Ok(());
+        }
+        Err(e) => {
+            return Err(format!(
+                "Failed to read sidecar checksum {:?}: {}",
+                checksum_path, e
+            ))
+        }
+    };
+    let actual = sha256_file(sidecar_path)
+        .map_err(|e| format!("Failed to hash sidecar binary {:?}: {}", sidecar_path, e))?;
+    if !actual.eq_ignore_ascii_case(&expected) {
+        return Err(format!(
+            "Sidecar checksum mismatch for {:?}: expected {}, got {} (set {}=false to skip the check)",
+            sidecar_path, expected, actual, ENV_VERIFY_SIDECAR_CHECKSUM
+        ));
+    }
+    info!("Sidecar checksum verified ({})", actual);
+    Ok(())
+}
+
+/// Give the sidecar binary its execute bits back (`0o755`) when they were
+/// lost, e.g. by extracting it from a DMG or AppImage
+#[cfg(unix)]
+fn ensure_executable(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = fs::metadata(path)
+        .map_err(|e| format!("Failed to read permissions of {:?}: {}", path, e))?
+        .permissions()
+        .mode();
+    if mode & 0o111 != 0 {
+        return Ok(());
+    }
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).map_err(|e| {
+        format!(
+            "Sidecar binary {:?} is not executable (mode {:o}) and could not be made executable: {}",
+            path,
+            mode & 0o7777,
+            e
+        )
+    })?;
+    warn!(
+        "Sidecar binary {:?} was not executable (mode {:o}); set it to 755",
+        path,
+        mode & 0o7777
+    );
+    Ok(())
+}
+
+/// Verify the installed sidecar bundle (production builds only)
+async fn verify_installed_bundle(app: &tauri::AppHandle) -> Result<BundleReport, String> {
+    if is_dev_mode() {
+        return Err("Bundle verification only applies to production builds".to_string());
+    }
+    let dir_name = get_sidecar_dir_name()?;
+    let bundle_dir = sidecar_resource_roots(app)?
+        .into_iter()
+        .map(|root| root.join("binaries").join(&dir_name))
+        .find(|dir| dir.is_dir())
+        .ok_or_else(|| format!("Sidecar bundle {} not found", dir_name))?;
+    tauri::async_runtime::spawn_blocking(move || verify_bundle(&bundle_dir))
+        .await
+        .map_err(|e| format!("Bundle verification failed: {}", e))?
+}
+
+/// Whether a startup error points at missing or damaged bundled files
+/// rather than e.g. a port conflict
+fn looks_like_broken_bundle(error: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "Sidecar binary not found",
+        "Failed to spawn sidecar",
+        "[PYI-",
+        "Failed to execute script",
+        "Failed to load Python",
+        "ModuleNotFoundError",
+        "ImportError",
+        "cannot open shared object file",
+        "Library not loaded",
+        "DLL load failed",
+    ];
+    MARKERS.iter().any(|marker| error.contains(marker))
+}
+
+/// Output buffering, backend log level and extra environment from the
+/// settings; the extra environment is applied last so it can override
+fn apply_backend_env(command: &mut Command, config: &BackendConfig) {
+    if config.python_unbuffered {
+        command.env("PYTHONUNBUFFERED", "1");
+    }
+    if let Some(level) = &config.backend_log_level {
+        command.env(ENV_BACKEND_LOG_LEVEL, level);
+    }
+    // Values are passed as-is, so spaces and `=` need no quoting
+    for (key, value) in config.env_file_vars.iter().chain(&config.extra_env) {
+        if is_reserved_backend_env(key) {
+            warn!("Not overriding {} for the backend", key);
+        } else {
+            command.env(key, value);
+        }
+    }
+}
+
+/// Whether `key` is in `RESERVED_BACKEND_ENV` (names are case-insensitive
+/// on Windows, so compare them that way everywhere)
+fn is_reserved_backend_env(key: &str) -> bool {
+    RESERVED_BACKEND_ENV
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(key))
+}
+
+/// Parse `KEY=VALUE` lines. Blank lines and `#` comments are skipped, an
+/// `export ` prefix is allowed, the value is everything after the first `=`
+/// (so it may contain spaces and `=`), and one pair of surrounding quotes
+/// is removed.
+fn parse_env_file(text: &str) -> HashMap<String, String> {
+    let mut vars = HashMap::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            warn!(
+                "Ignoring line {} of the backend env file: no '='",
+                number + 1
+            );
+            continue;
+        };
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            warn!(
+                "Ignoring line {} of the backend env file: invalid name {:?}",
+                number + 1,
+                key
+            );
+            continue;
+        }
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|quote| value.strip_prefix(*quote)?.strip_suffix(*quote))
+            .unwrap_or(value);
+        vars.insert(key.to_string(), value.to_string());
+    }
+    vars
+}
+
+/// Console and detach flags for the backend process
+fn configure_process_flags(command: &mut Command, config: &BackendConfig) {
+    #[cfg(unix)]
+    if config.detached {
+        // Own process group, so signals aimed at the GUI's group don't reach it
+        std::os::unix::process::CommandExt::process_group(command, 0);
+    }
+    #[cfg(windows)]
+    if config.detached {
+        command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+    } else if !config.show_console {
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+}
+
+/// A freshly spawned backend process
+struct SpawnedSidecar {
+    handle: ProcessHandle,
+    log_paths: Option<LogPaths>,
+    /// Lengths of the log files just before the spawn; the new run's output
+    /// starts here
+    log_start: u64,
+    stderr_log_start: u64,
+    /// Log files replaced by a timestamped one, and why
+    log_reopened: Vec<(PathBuf, String)>,
+    /// Offset of the restart marker line written to the stdout log before
+    /// this run
+    restart_marker: Option<u64>,
+}
+
+/// Log files set up for one backend run
+struct SidecarLog {
+    paths: LogPaths,
+    stdout: fs::File,
+    stderr: fs::File,
+    /// Where this run's output starts in each file
+    stdout_start: u64,
+    stderr_start: u64,
+    reopened: Vec<(PathBuf, String)>,
+    restart_marker: Option<u64>,
+}
+
+/// One stream's log file, opened for a backend run
+struct RunLog {
+    path: PathBuf,
+    file: fs::File,
+    start: u64,
+    reopened: Option<String>,
+    restart_marker: Option<u64>,
+}
+
+/// Open the logs for a backend run.
+///
+/// A restart passes the previous run's logs, which are appended to after a
+/// `--- backend restarted at ... ---` line so history and frontend offsets
+/// carry across restarts instead of starting new files. A log that grew
+/// past `log_max_bytes` is rotated first (see `rotate_log_if_large`).
+fn open_sidecar_log(
+    app: &tauri::AppHandle,
+    config: &BackendConfig,
+    previous_log_paths: Option<LogPaths>,
+) -> Result<SidecarLog, String> {
+    let restart = previous_log_paths.is_some();
+    let paths = previous_log_paths.unwrap_or_else(|| {
+        let dir = resolve_backend_log_dir(app);
+        LogPaths {
+            stdout: dir.join(BACKEND_STDOUT_LOG_FILE_NAME),
+            stderr: dir.join(BACKEND_STDERR_LOG_FILE_NAME),
+        }
+    });
+    let stdout = open_run_log(paths.stdout, config, restart)?;
+    let stderr = open_run_log(paths.stderr, config, restart)?;
+    let reopened = [&stdout, &stderr]
+        .iter()
+        .filter_map(|log| Some((log.path.clone(), log.reopened.clone()?)))
+        .collect();
+    Ok(SidecarLog {
+        paths: LogPaths {
+            stdout: stdout.path,
+            stderr: stderr.path,
+        },
+        stdout: stdout.file,
+        stderr: stderr.file,
+        stdout_start: stdout.start,
+        stderr_start: stderr.start,
+        reopened,
+        restart_marker: stdout.restart_marker,
+    })
+}
+
+/// Rotate and open one stream's log, writing the restart marker on a restart
+fn open_run_log(
+    log_path: PathBuf,
+    config: &BackendConfig,
+    restart: bool,
+) -> Result<RunLog, String> {
+    if let Some(parent) = log_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create backend log dir {:?}: {}", parent, e))?;
+    }
+    match rotate_log_if_large(&log_path, config.log_max_bytes, config.log_keep) {
+        Ok(true) => info!("Rotated backend log {:?}", log_path),
+        Ok(false) => {}
+        Err(e) => warn!("Failed to rotate backend log {:?}: {}", log_path, e),
+    }
+    let (path, file, reopened) = open_backend_log_or_fallback(log_path, config.log_keep)?;
+    let mut start = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let mut restart_marker = None;
+    if restart {
+        match write_restart_marker(&path, &file, start) {
+            Ok((marker, end)) => {
+                restart_marker = Some(marker);
+                start = end;
+            }
+            Err(e) => warn!("Failed to write restart marker to {:?}: {}", path, e),
+        }
+    }
+    Ok(RunLog {
+        path,
+        file,
+        start,
+        reopened,
+        restart_marker,
+    })
+}
+
+/// `<path>.<n>`, the `n`th most recent rotated copy of a log
+fn rotated_log_path(path: &Path, n: u32) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+/// If the log at `path` is larger than `max_bytes`, shift `<path>.1` ..
+/// `<path>.<keep - 1>` up by one (dropping `<path>.<keep>`) and move the
+/// log to `<path>.1`, so the next open starts a fresh file. Log streams and
+/// `read_next` readers notice the new file at `path` and start over at its
+/// top. Returns whether it rotated.
+fn rotate_log_if_large(path: &Path, max_bytes: u64, keep: u32) -> std::io::Result<bool> {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.len() > max_bytes => {}
+        Ok(_) => return Ok(false),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    }
+    if keep == 0 {
+        fs::remove_file(path)?;
+        return Ok(true);
+    }
+    let _ = fs::remove_file(rotated_log_path(path, keep));
+    for n in (1..keep).rev() {
+        let from = rotated_log_path(path, n);
+        if from.exists() {
+            fs::rename(&from, rotated_log_path(path, n + 1))?;
+        }
+    }
+    fs::rename(path, rotated_log_path(path, 1))?;
+    Ok(true)
+}
+
+/// Append the restart marker line to a log of length `len`, starting a new
+/// line first if the last run stopped mid-line. Returns the marker line's
+/// offset and the new end of the log.
+fn write_restart_marker(path: &Path, mut log: &fs::File, len: u64) -> std::io::Result<(u64, u64)> {
+    use std::io::Write;
+
+    let mut prefix = "";
+    if len > 0 {
+        let mut file = fs::File::open(path)?;
+        file.seek(SeekFrom::Start(len - 1))?;
+        let mut last = [0u8; 1];
+        file.read_exact(&mut last)?;
+        if last[0] != b'\n' {
+            prefix = "\n";
+        }
+    }
+    let line = format!(
+        "--- backend restarted at {} ---\n",
+        format_utc_timestamp(unix_time_ms())
+    );
+    log.write_all(format!("{}{}", prefix, line).as_bytes())?;
+    let marker = len + prefix.len() as u64;
+    Ok((marker, marker + line.len() as u64))
+}
+
+/// Format milliseconds since the Unix epoch as `YYYY-MM-DD HH:MM:SS UTC`
+fn format_utc_timestamp(ms: u64) -> String {
+    let secs = ms / 1000;
+    let (days, rem) = ((secs / 86_400) as i64, secs % 86_400);
+    // Civil date from a day count (Howard Hinnant's days_from_civil inverse)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// Start the Python backend sidecar process
+async fn start_sidecar(
+    app: &tauri::AppHandle,
+    config: &BackendConfig,
+    port: u16,
+    previous_log_paths: Option<LogPaths>,
+) -> Result<SpawnedSidecar, String> {
+    if config.mock {
+        let mock = MockBackend::start(BACKEND_HOST, port)?;
+        warn!(
+            "{} is set; serving the mock backend on port {} instead of Python",
+            ENV_MOCK_BACKEND, mock.port
+        );
+        return Ok(SpawnedSidecar {
+            handle: ProcessHandle::Mock(mock),
+            log_paths: None,
+            log_start: 0,
+            stderr_log_start: 0,
+            log_reopened: Vec::new(),
+            restart_marker: None,
+        });
+    }
+
+    let config_args = config.config_file_args()?;
+    if let Some(path) = &config.uds_path {
+        prepare_uds_path(path)?;
+    }
+    let listen_args = backend_listen_args(config, port);
+    let worker_args = uvicorn_worker_args(config);
+
+    if is_dev_mode() {
+        info!("Starting backend in development mode");
+        let backend_dir = get_dev_backend_dir(app)?;
+
+        if env_var_non_empty(ENV_BACKEND_DIR).is_some() {
+            info!(
+                "Backend directory: {:?} (from {})",
+                backend_dir, ENV_BACKEND_DIR
+            );
+        } else {
+            info!("Backend directory: {:?}", backend_dir);
+        }
+
+        // Verify backend directory exists
+        if !backend_dir.exists() {
+            return Err(format!(
+                "Backend directory does not exist: {:?}",
+                backend_dir
+            ));
+        }
+        validate_uvicorn_app(&backend_dir, &config.uvicorn_app)?;
+
+        let launchers = match config.launcher {
+            Launcher::Auto => config.launcher_order.clone(),
+            launcher => vec![launcher],
+        };
+        let log = open_sidecar_log(app, config, previous_log_paths)?;
+        let app_state = app.state::<Arc<AppState>>();
+
+        let mut failures = Vec::new();
+        for launcher in launchers {
+            let (program, prefix): (PathBuf, &[&str]) = match launcher {
+                Launcher::Auto => continue,
+                Launcher::Venv => match find_dev_python(&backend_dir) {
+                    Some(python) => (python, &["-m", "uvicorn"]),
+                    None => {
+                        failures.push(format!(
+                            "venv: no virtualenv Python under {:?}",
+                            backend_dir.join(".venv")
+                        ));
+                        continue;
+                    }
+                },
+                Launcher::Uv => {
+                    let cached = app_state.uv_path.lock().await.clone();
+                    let uv_path = match cached {
+                        Some(path) => Ok(path),
+                        None => {
+                            // The probes wait on child processes; keep them off the runtime
+                            let verify = config.verify_uv;
+                            tauri::async_runtime::spawn_blocking(move || find_uv_path(verify))
+                                .await
+                                .unwrap_or_else(|e| Err(format!("uv lookup failed: {}", e)))
+                        }
+                    };
+                    match uv_path {
+                        Ok(path) => {
+                            *app_state.uv_path.lock().await = Some(path.clone());
+                            (PathBuf::from(path), &["run", "uvicorn"])
+                        }
+                        Err(e) => {
+                            failures.push(format!("uv: {}", e));
+                            continue;
+                        }
+                    }
+                }
+                Launcher::Python => match tauri::async_runtime::spawn_blocking(find_system_python)
+                    .await
+                    .unwrap_or_else(|e| Err(format!("Python lookup failed: {}", e)))
+                {
+                    Ok(python) => (PathBuf::from(python), &["-m", "uvicorn"]),
+                    Err(e) => {
+                        failures.push(format!("python: {}", e));
+                        continue;
+                    }
+                },
+            };
+
+            let mut command = Command::new(&program);
+            command
+                .args(prefix)
+                .arg(&config.uvicorn_app)
+                .args(&listen_args);
+            if config.uvicorn_reload {
+                info!("Running uvicorn with --reload");
+                command.arg("--reload");
+            }
+            command.args(&worker_args).args(&config_args);
+
+            configure_process_flags(&mut command, config);
+            apply_backend_env(&mut command, config);
+
+            let (stdout, stderr) = log
+                .stdout
+                .try_clone()
+                .and_then(|stdout| Ok((stdout, log.stderr.try_clone()?)))
+                .map_err(|e| format!("Failed to share backend log files: {}", e))?;
+            command
+                .current_dir(&backend_dir)
+                .stdout(Stdio::from(stdout))
+                .stderr(Stdio::from(stderr));
+            match spawn_with_retry(&mut command).await {
+                Ok(child) => {
+                    info!(
+                        "Backend process started with PID {:?} using the {} launcher ({:?})",
+                        child.id(),
+                        launcher.name(),
+                        program
+                    );
+                    info!("Backend log paths: {:?}", log.paths);
+                    *app_state.active_launcher.lock().await = Some(launcher);
+                    return Ok(SpawnedSidecar {
+                        handle: ProcessHandle::StdChild(child),
+                        log_paths: Some(log.paths),
+                        log_start: log.stdout_start,
+                        stderr_log_start: log.stderr_start,
+                        log_reopened: log.reopened,
+                        restart_marker: log.restart_marker,
+                    });
+                }
+                Err(e) => {
+                    warn!("Failed to spawn the {} launcher: {}", launcher.name(), e);
+                    failures.push(format!(
+                        "{}: failed to spawn {:?}: {}",
+                        launcher.name(),
+                        program,
+                        e
+                    ));
+                }
+            }
+        }
+        Err(format!(
+            "Could not start the backend with any launcher:\n  {}",
+            failures.join("\n  ")
+        ))
+    } else {
+        // Production mode: use bundled sidecar from resources
+        // The sidecar is built with PyInstaller --onedir and needs _internal next to it
+        info!("Starting backend in production mode with bundled sidecar");
+        if config.uvicorn_reload {
+            warn!(
+                "{} is only supported in development mode",
+                ENV_UVICORN_RELOAD
+            );
+        }
+
+        let resource_roots = sidecar_resource_roots(app)?;
+        let sidecar_dir_name = get_sidecar_dir_name()?;
+        let sidecar_binary_name = get_sidecar_binary_name()?;
+
+        let mut selected_sidecar: Option<(PathBuf, PathBuf)> = None;
+        for root in &resource_roots {
+            let sidecar_dir = root.join("binaries").join(&sidecar_dir_name);
+            let sidecar_path = sidecar_dir.join(&sidecar_binary_name);
+            if sidecar_path.exists() {
+                selected_sidecar = Some((sidecar_dir, sidecar_path));
+                break;
+            }
+        }
+
+        let (sidecar_dir, sidecar_path) = selected_sidecar.ok_or_else(|| {
+            let roots = resource_roots
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "Sidecar binary not found. checked_roots=[{}], sidecar_dir_name={}, sidecar_binary_name={}",
+                roots, sidecar_dir_name, sidecar_binary_name
+            )
+        })?;
+
+        info!("Sidecar directory: {:?}", sidecar_dir);
+        info!("Sidecar path: {:?}", sidecar_path);
+
+        #[cfg(unix)]
+        ensure_executable(&sidecar_path)?;
+
+        if config.verify_sidecar_checksum {
+            let (dir, path) = (sidecar_dir.clone(), sidecar_path.clone());
+            tauri::async_runtime::spawn_blocking(move || verify_sidecar_checksum(&dir, &path))
+                .await
+                .map_err(|e| format!("Sidecar checksum check failed: {}", e))??;
+        } else {
+            warn!(
+                "{} is false; launching the sidecar without checking it",
+                ENV_VERIFY_SIDECAR_CHECKSUM
+            );
+        }
+
+        // Start the sidecar process
+        // Must run from sidecar_dir so it can find _internal
+        let log = open_sidecar_log(app, config, previous_log_paths)?;
+
+        let mut command = Command::new(&sidecar_path);
+        command
+            .args(&listen_args)
+            .args(&worker_args)
+            .args(&config_args)
+            .current_dir(&sidecar_dir)
+            .stdout(Stdio::from(log.stdout))
+            .stderr(Stdio::from(log.stderr));
+
+        configure_process_flags(&mut command, config);
+        apply_backend_env(&mut command, config);
+
+        let child = spawn_with_retry(&mut command)
+            .await
+            .map_err(|e| format!("Failed to spawn sidecar (it never started): {}", e))?;
+
+        info!("Backend process started with PID: {:?}", child.id());
+        info!("Backend log paths: {:?}", log.paths);
+
+        Ok(SpawnedSidecar {
+            handle: ProcessHandle::StdChild(child),
+            log_paths: Some(log.paths),
+            log_start: log.stdout_start,
+            stderr_log_start: log.stderr_start,
+            log_reopened: log.reopened,
+            restart_marker: log.restart_marker,
+        })
+    }
+}
+
+/// Whether a spawn error may go away on its own: the system is briefly out
+/// of processes or memory, or the executable is still open for writing
+fn is_transient_spawn_error(error: &std::io::Error) -> bool {
+    use std::io::ErrorKind;
+    if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::Interrupted) {
+        return true;
+    }
+    // ETXTBSY, right after the binary was written or chmod-ed
+    #[cfg(unix)]
+    if error.raw_os_error() == Some(26) {
+        return true;
+    }
+    false
+}
+
+/// Spawn `command`, retrying transient failures up to
+/// `SPAWN_RETRY_ATTEMPTS` times; any other error is returned at once
+async fn spawn_with_retry(command: &mut Command) -> std::io::Result<Child> {
+    let mut delay = Duration::from_millis(SPAWN_RETRY_INITIAL_DELAY_MS);
+    let mut attempt = 1;
+    loop {
+        match command.spawn() {
+            Ok(child) => return Ok(child),
+            Err(e) if attempt < SPAWN_RETRY_ATTEMPTS && is_transient_spawn_error(&e) => {
+                warn!(
+                    "Spawning the backend failed (attempt {}/{}): {}; retrying in {} ms",
+                    attempt,
+                    SPAWN_RETRY_ATTEMPTS,
+                    e,
+                    delay.as_millis()
+                );
+                sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) if attempt > 1 => {
+                return Err(std::io::Error::new(
+                    e.kind(),
+                    format!("{} (after {} attempts)", e, attempt),
+                ))
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Why the startup health wait ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum StartupWaitEnd {
+    /// The health timeout passed (or the server was already answering polls)
+    Timeout,
+    /// The backend log was quiet for `STARTUP_QUIET_TIMEOUT_SECS`
+    Quiet,
+    /// The log was still growing at `MAX_HEALTH_CHECK_TIMEOUT_SECS`
+    Limit,
+}
+
+/// Whether the startup wait should end, `elapsed` into it with the backend
+/// log last growing `quiet_for` ago and `answered` telling whether any
+/// health poll got an HTTP response
+fn startup_wait_end(
+    elapsed: Duration,
+    timeout: Duration,
+    quiet_for: Duration,
+    answered: bool,
+) -> Option<StartupWaitEnd> {
+    let max_timeout = Duration::from_secs(MAX_HEALTH_CHECK_TIMEOUT_SECS).max(timeout);
+    if elapsed < timeout {
+        None
+    } else if answered {
+        Some(StartupWaitEnd::Timeout)
+    } else if quiet_for >= Duration::from_secs(STARTUP_QUIET_TIMEOUT_SECS) {
+        Some(StartupWaitEnd::Quiet)
+    } else if elapsed >= max_timeout {
+        Some(StartupWaitEnd::Limit)
+    } else {
+        None
+    }
+}
+
+/// Wait for the backend to become ready by polling the health endpoint
+async fn wait_for_backend(app: &tauri::AppHandle, state: &Arc<AppState>) -> Result<(), String> {
+    let client = &state.http_client;
+
+    let start = std::time::Instant::now();
+    let timeout_secs = state.config.lock().await.health_timeout_secs;
+    let timeout = Duration::from_secs(timeout_secs);
+    let health_urls = state.startup_health_urls();
+    let config = state.config.lock().await.clone();
+    let identity_marker = config.identity_marker.clone();
+    let mut identity_mismatch_reported = false;
+    let mut attempts = 0;
+    let mut last_emitted: Option<std::time::Instant> = None;
+    let mut interval_ms = HEALTH_CHECK_INITIAL_INTERVAL_MS;
+    let mut jitter = JitterRng::new();
+    let mut log_len = startup_log_len(state).await;
+    let mut last_progress = start;
+    let mut answered = false;
+    let mut extension_reported = false;
+
+    info!("Waiting for backend to become ready at {}", health_urls[0]);
+
+    let end = loop {
+        let current_len = startup_log_len(state).await;
+        if current_len != log_len {
+            log_len = current_len;
+            last_progress = std::time::Instant::now();
+        }
+        let elapsed = start.elapsed();
+        if let Some(end) = startup_wait_end(elapsed, timeout, last_progress.elapsed(), answered) {
+            break end;
+        }
+        if elapsed >= timeout && !extension_reported {
+            extension_reported = true;
+            info!(
+                "Backend log is still growing after {} seconds; waiting until it is quiet for {} seconds",
+                timeout_secs, STARTUP_QUIET_TIMEOUT_SECS
+            );
+        }
+
+        if state.startup_cancelled.load(Ordering::SeqCst) {
+            return Err("Backend startup cancelled by user".to_string());
+        }
+        if let Some(exit_error) = check_sidecar_exited(state).await {
+            return Err(exit_error);
+        }
+
+        attempts += 1;
+        let mut last_outcome = None;
+        for url in &health_urls {
+            match client
+                .request(config.health_method.clone(), url)
+                .send()
+                .await
+            {
+                Ok(response) => {
+                    answered = true;
+                    if config.is_healthy_status(response.status()) {
+                        let Some(marker) = &identity_marker else {
+                            info!("Backend is ready at {}", url);
+                            return Ok(());
+                        };
+                        let headers = response.headers().clone();
+                        let body = response.bytes().await.unwrap_or_default();
+                        if marker.matches(&headers, &body) {
+                            info!(
+                                "Backend is ready at {} (identity {} confirmed)",
+                                url, marker
+                            );
+                            return Ok(());
+                        }
+                        if !identity_mismatch_reported {
+                            identity_mismatch_reported = true;
+                            warn!(
+                                "Server at {} answered health but lacks identity marker {}",
+                                url, marker
+                            );
+                            emit_backend_event(
+                                app,
+                                state,
+                                "backend-identity-mismatch",
+                                serde_json::json!({
+                                    "url": url,
+                                    "expected": marker.to_string(),
+                                }),
+                            )
+                            .await;
+                        }
+                        last_outcome = Some(format!("identity marker {} missing", marker));
+                        continue;
+                    }
+                    warn!(
+                        "Backend returned non-success status at {}: {}",
+                        url,
+                        response.status()
+                    );
+                    last_outcome = Some(format!("HTTP {}", response.status()));
+                }
+                Err(e) => {
+                    // Connection refused is expected while backend is starting
+                    if !e.is_connect() {
+                        warn!("Health check failed at {}: {}", url, e);
+                        last_outcome = Some(e.to_string());
+                    } else if last_outcome.is_none() {
+                        last_outcome = Some("connection refused".to_string());
+                    }
+                }
+            }
+        }
+
+        // Push at most one event per second; `startup_progress` always has
+        // the latest poll
+        let emit = last_emitted.map_or(true, |at| at.elapsed() >= Duration::from_secs(1));
+        if emit {
+            last_emitted = Some(std::time::Instant::now());
+        }
+        publish_startup_progress(
+            app,
+            state,
+            StartupProgress {
+                phase: StartupPhase::WaitingForHealth,
+                elapsed_ms: start.elapsed().as_millis() as u64,
+                attempts,
+                timeout_secs,
+                last_outcome,
+            },
+            emit,
+        )
+        .await;
+
+        let delay_ms = jitter.jitter(interval_ms, HEALTH_CHECK_JITTER_PERCENT);
+        sleep(Duration::from_millis(delay_ms)).await;
+        interval_ms = (interval_ms * 2).min(HEALTH_CHECK_MAX_INTERVAL_MS);
+    };
+
+    let waited_secs = start.elapsed().as_secs();
+    let mut error_message = match end {
+        StartupWaitEnd::Limit => format!(
+            "Backend failed to start within {} seconds (log output was still growing when the {}-second limit was reached)",
+            waited_secs,
+            MAX_HEALTH_CHECK_TIMEOUT_SECS.max(timeout_secs)
+        ),
+        StartupWaitEnd::Quiet if waited_secs > timeout_secs => format!(
+            "Backend failed to start within {} seconds (no log output for the last {} seconds)",
+            waited_secs, STARTUP_QUIET_TIMEOUT_SECS
+        ),
+        _ => format!("Backend failed to start within {} seconds", waited_secs),
+    };
+    if let Some(log_tail) = read_backend_error_tail(state, 80).await {
+        error_message.push('\n');
+        error_message.push_str(&log_tail);
+    }
+    Err(error_message)
+}
+
+/// Combined size of the backend logs, to notice output during startup
+async fn startup_log_len(state: &AppState) -> u64 {
+    let Some(paths) = state.log_paths().await else {
+        return 0;
+    };
+    [paths.stdout, paths.stderr]
+        .iter()
+        .filter_map(|path| fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+/// Store startup progress in `startup_progress` and optionally push it as
+/// a `backend-progress` event
+async fn publish_startup_progress(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    progress: StartupProgress,
+    emit: bool,
+) {
+    state.startup_progress.send_replace(progress.clone());
+    if emit {
+        if let Err(e) = app.emit("backend-progress", progress) {
+            warn!("Failed to emit backend-progress event: {}", e);
+        }
+    }
+}
+
+/// Mark the current start as finished, keeping its counters
+async fn finish_startup_progress(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    phase: StartupPhase,
+    outcome: Option<String>,
+) {
+    let mut progress = state.startup_progress.borrow().clone();
+    progress.phase = phase;
+    if let Some(outcome) = outcome {
+        progress.last_outcome = Some(outcome);
+    }
+    publish_startup_progress(app, state, progress, true).await;
+}
+
+/// Pre-flight bind check; returns the bind error when the port is unusable
+fn try_bind(port: u16) -> std::io::Result<()> {
+    std::net::TcpListener::bind((BACKEND_HOST, port)).map(drop)
+}
+
+/// Poll with bind attempts until `port` is free, up to `timeout`, emitting
+/// `backend-port-wait` while it is still taken.
+///
+/// Returns whether the port can be bound. Errors other than "address in
+/// use" end the wait early since waiting won't fix them; the launch that
+/// follows reports them.
+async fn wait_for_port_release(app: &tauri::AppHandle, port: u16, timeout: Duration) -> bool {
+    let start = std::time::Instant::now();
+    let mut attempts: u32 = 0;
+    loop {
+        attempts += 1;
+        match try_bind(port) {
+            Ok(()) => {
+                if attempts > 1 {
+                    info!(
+                        "Port {} released after {} ms",
+                        port,
+                        start.elapsed().as_millis()
+                    );
+                }
+                return true;
+            }
+            Err(e) if e.kind() != std::io::ErrorKind::AddrInUse => return false,
+            Err(_) => {}
+        }
+        if start.elapsed() >= timeout {
+            warn!(
+                "Port {} still in use after {} ms",
+                port,
+                timeout.as_millis()
+            );
+            return false;
+        }
+        let progress = serde_json::json!({
+            "port": port,
+            "attempts": attempts,
+            "elapsed_ms": start.elapsed().as_millis() as u64,
+            "timeout_ms": timeout.as_millis() as u64,
+        });
+        if let Err(e) = app.emit("backend-port-wait", progress) {
+            warn!("Failed to emit backend-port-wait event: {}", e);
+        }
+        sleep(Duration::from_millis(PORT_RELEASE_POLL_MS)).await;
+    }
+}
+
+/// Pick the backend port: the last successfully used one if it is still free
+/// (and within the configured range), otherwise the first free candidate.
+///
+/// Only "address in use" moves on to the next candidate; permission and
+/// address errors would fail the same way for every port.
+fn select_backend_port(
+    config: &BackendConfig,
+    last_port: Option<u16>,
+    exclude: &[u16],
+) -> Result<u16, String> {
+    let candidates = config.port_candidates();
+    let preferred = last_port.filter(|port| candidates.contains(port));
+    for port in preferred
+        .into_iter()
+        .chain(candidates.clone())
+        .filter(|port| !exclude.contains(port))
+    {
+        match try_bind(port) {
+            Ok(()) => {
+                if port != config.port {
+                    info!("Using backend port {} (preferred {})", port, config.port);
+                }
+                return Ok(port);
+            }
+            Err(e) => match ErrorCategory::from_io_kind(e.kind()) {
+                Some(ErrorCategory::AddressInUse) => {}
+                Some(category) => {
+                    return Err(format!("{} ({})", category.message(BACKEND_HOST, port), e));
+                }
+                None => warn!("Cannot bind {}:{}: {}", BACKEND_HOST, port, e),
+            },
+        }
+    }
+    let holder = find_port_owner(config.port)
+        .map(|owner| format!(" (port {} is held by {})", config.port, owner))
+        .unwrap_or_default();
+    Err(format!(
+        "No free backend port in {}-{}{}",
+        candidates.start(),
+        candidates.end(),
+        holder
+    ))
+}
+
+/// Whether `pid` is `root` or one of its descendants
+fn is_same_process_tree(root: u32, pid: u32) -> bool {
+    if root == pid {
+        return true;
+    }
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    collect_descendants(&sys, root).contains(&pid)
+}
+
+/// Reattach to the detached backend recorded by a previous GUI run.
+///
+/// The recorded PID must still be alive, own the listening socket (where
+/// the OS lets us check) and answer health as our backend. Otherwise the
+/// record is dropped and the caller spawns a fresh backend; a recorded
+/// process that holds the port but fails the health check is killed first
+/// so the two don't run side by side.
+async fn try_reattach_detached(
+    app: &tauri::AppHandle,
+    state: &Arc<AppState>,
+    config: &BackendConfig,
+) -> Option<u32> {
+    let record = read_detached_record(app)?;
+    if !process_exists(record.pid) {
+        info!("Detached backend PID {} is gone", record.pid);
+        remove_detached_record(app);
+        return None;
+    }
+    let owns_port = match find_listening_pid(record.port) {
+        Some(listener) if !is_same_process_tree(record.pid, listener) => {
+            warn!(
+                "Port {} is held by PID {}, not the detached backend PID {}",
+                record.port, listener, record.pid
+            );
+            remove_detached_record(app);
+            return None;
+        }
+        Some(_) => true,
+        None => false,
+    };
+
+    let url = format!(
+        "{}{}",
+        state.base_url_at(&state.backend_host, record.port),
+        state.health_path
+    );
+    let response = match state
+        .http_client
+        .request(config.health_method.clone(), &url)
+        .send()
+        .await
+    {
+        Ok(response) if config.is_healthy_status(response.status()) => response,
+        outcome => {
+            let reason = match outcome {
+                Ok(response) => format!("HTTP {}", response.status()),
+                Err(e) => e.to_string(),
+            };
+            remove_detached_record(app);
+            // Only a process verified to hold the port is ours to stop; a
+            // PID that merely exists may have been reused
+            if owns_port {
+                warn!(
+                    "Detached backend PID {} on port {} is unhealthy ({}); stopping it",
+                    record.pid, record.port, reason
+                );
+                kill_process_tree(record.pid);
+            } else {
+                warn!(
+                    "Detached backend PID {} does not answer on port {} ({}); not reattaching and leaving the process alone",
+                    record.pid, record.port, reason
+                );
+            }
+            return None;
+        }
+    };
+    let headers = response.headers().clone();
+    let body = response.bytes().await.unwrap_or_default();
+    let identified = match &config.identity_marker {
+        Some(marker) => marker.matches(&headers, &body),
+        None => serde_json::from_slice::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|body| Some(body.get("app")?.as_str()? == "alproj"))
+            .unwrap_or(false),
+    };
+    if !identified {
+        warn!(
+            "Server at {} is not the detached backend; starting a new one",
+            url
+        );
+        remove_detached_record(app);
+        return None;
+    }
+
+    info!(
+        "Reattached to detached backend PID {} on port {}",
+        record.pid, record.port
+    );
+    state.backend_port.store(record.port, Ordering::SeqCst);
+    state.sidecars.lock().await.insert(
+        MAIN_SERVICE.to_string(),
+        ProcessHandle::Attached { pid: record.pid },
+    );
+    emit_backend_event(app, state, "backend-port", record.port).await;
+    finish_startup_progress(app, state, StartupPhase::Ready, None).await;
+    *state.backend_ready.lock().await = true;
+    state.set_backend_state(BackendState::Ready).await;
+    emit_backend_event(app, state, "backend-ready", true).await;
+    tauri::async_runtime::spawn(monitor_backend(app.clone(), state.clone()));
+    Some(record.pid)
+}
+
+/// Launchers after the active one in `launcher_order`, when the dev-mode
+/// backend it started has already exited and `launcher` is `auto`
+async fn remaining_launchers(state: &AppState, config: &BackendConfig) -> Option<Vec<Launcher>> {
+    if !is_dev_mode() || config.launcher != Launcher::Auto {
+        return None;
+    }
+    let exited = state
+        .sidecars
+        .lock()
+        .await
+        .get_mut(MAIN_SERVICE)
+        .is_some_and(|handle| !service_running(handle));
+    if !exited {
+        return None;
+    }
+    let active = (*state.active_launcher.lock().await)?;
+    let remaining: Vec<Launcher> = config
+        .launcher_order
+        .iter()
+        .skip_while(|launcher| **launcher != active)
+        .skip(1)
+        .copied()
+        .collect();
+    (!remaining.is_empty()).then_some(remaining)
+}
+
+/// Spawn the backend, wait until it is ready and notify the frontend.
+///
+/// Emits `backend-ready` (and starts the liveness monitor) on success or
+/// `backend-error` on failure. Returns the new backend PID. A backend that
+/// exits because its port was taken is respawned on the next free port (see
+/// `BIND_RETRY_ATTEMPTS`), announced with `backend-port-retry`.
+async fn launch_backend(
+    app: &tauri::AppHandle,
+    state: &Arc<AppState>,
+) -> Result<Option<u32>, String> {
+    let mut config = state.config.lock().await.clone();
+    let launch_start = std::time::Instant::now();
+    state.startup_cancelled.store(false, Ordering::SeqCst);
+    state.set_backend_state(BackendState::Starting).await;
+    if config.detached {
+        if let Some(pid) = try_reattach_detached(app, state, &config).await {
+            return Ok(Some(pid));
+        }
+    }
+
+    let last_port = load_persisted_settings(app).last_port;
+    let port_choice = if config.uds_path.is_some() {
+        // Nothing binds the port; it only fills the (ignored) URL authority
+        Ok(config.port)
+    } else if config.attach {
+        // The external backend already holds it
+        Ok(config.port)
+    } else {
+        select_backend_port(&config, last_port, &[])
+    };
+    let mut port = match port_choice {
+        Ok(port) => port,
+        Err(e) => {
+            error!("{}", e);
+            state.set_backend_state(BackendState::Failed).await;
+            emit_backend_event(app, state, "backend-error", e.clone()).await;
+            return Err(e);
+        }
+    };
+    state.backend_port.store(port, Ordering::SeqCst);
+    emit_backend_event(app, state, "backend-port", port).await;
+
+    // A restart keeps writing to the previous run's logs. Read once: a retry
+    // within this launch is not a restart and gets no restart marker.
+    let previous_log_paths = state.log_paths().await;
+    let mut tried_ports = Vec::new();
+    let mut attempt = 1;
+    let (pid, ready) = if config.attach {
+        info!(
+            "{} is set; waiting for an externally started backend instead of spawning one",
+            ENV_ATTACH
+        );
+        publish_startup_progress(
+            app,
+            state,
+            StartupProgress {
+                phase: StartupPhase::WaitingForHealth,
+                timeout_secs: config.health_timeout_secs,
+                ..Default::default()
+            },
+            true,
+        )
+        .await;
+        (None, wait_for_backend(app, state).await)
+    } else {
+        loop {
+            *state.startup_log.lock().await = StartupLog::default();
+            publish_startup_progress(
+                app,
+                state,
+                StartupProgress {
+                    phase: StartupPhase::Spawning,
+                    timeout_secs: config.health_timeout_secs,
+                    ..Default::default()
+                },
+                true,
+            )
+            .await;
+            let spawned = match start_sidecar(app, &config, port, previous_log_paths.clone()).await
+            {
+                Ok(spawned) => spawned,
+                Err(e) => {
+                    error!("Failed to start sidecar: {}", e);
+                    finish_startup_progress(app, state, StartupPhase::Failed, Some(e.clone()))
+                        .await;
+                    state.set_backend_state(BackendState::Failed).await;
+                    emit_backend_event(app, state, "backend-error", e.clone()).await;
+                    check_bundle_after_failure(app, state, &e);
+                    return Err(e);
+                }
+            };
+            let pid = spawned.handle.pid();
+            state
+                .sidecars
+                .lock()
+                .await
+                .insert(MAIN_SERVICE.to_string(), spawned.handle);
+            let stdout_log_path = spawned.log_paths.as_ref().map(|p| p.stdout.clone());
+            let stderr_log_path = spawned.log_paths.as_ref().map(|p| p.stderr.clone());
+            *state.backend_log_path.lock().await = stdout_log_path.clone();
+            *state.backend_stderr_log_path.lock().await = stderr_log_path.clone();
+            for (path, reason) in &spawned.log_reopened {
+                emit_backend_event(
+                    app,
+                    state,
+                    "backend-log-reopened",
+                    serde_json::json!({ "path": path, "reason": reason }),
+                )
+                .await;
+            }
+            if let (Some(offset), Some(path)) = (spawned.restart_marker, &stdout_log_path) {
+                emit_backend_event(
+                    app,
+                    state,
+                    "backend-log-restart-marker",
+                    serde_json::json!({ "path": path, "offset": offset }),
+                )
+                .await;
+            }
+            {
+                let mut startup_log = state.startup_log.lock().await;
+                startup_log.log_path = stdout_log_path;
+                startup_log.log_start = spawned.log_start;
+                startup_log.stderr_log_path = stderr_log_path;
+                startup_log.stderr_log_start = spawned.stderr_log_start;
+            }
+
+            let ready = wait_for_backend(app, state).await;
+            state.startup_log.lock().await.capture();
+            let Err(e) = &ready else {
+                break (pid, ready);
+            };
+            if state.startup_cancelled.load(Ordering::SeqCst) {
+                break (pid, ready);
+            }
+            if ErrorCategory::from_log(e) != Some(ErrorCategory::AddressInUse) {
+                // A dev launcher that started but exited (e.g. `uv run`
+                // failing to resolve dependencies) counts as failed too
+                let Some(remaining) = remaining_launchers(state, &config).await else {
+                    break (pid, ready);
+                };
+                let failed = state.active_launcher.lock().await.map(Launcher::name);
+                warn!(
+                    "The {} launcher exited during startup; trying {}",
+                    failed.unwrap_or("auto"),
+                    remaining[0].name()
+                );
+                emit_backend_event(
+                    app,
+                    state,
+                    "backend-launcher-fallback",
+                    serde_json::json!({
+                        "failed": failed,
+                        "next": remaining[0].name(),
+                        "reason": e.lines().next().unwrap_or_default(),
+                    }),
+                )
+                .await;
+                state.sidecars.lock().await.remove(MAIN_SERVICE);
+                config.launcher_order = remaining;
+                continue;
+            }
+            if attempt >= BIND_RETRY_ATTEMPTS || config.uds_path.is_some() {
+                break (pid, ready);
+            }
+
+            // Another process took the port after we probed it: drop this
+            // attempt and respawn on the next free candidate
+            if let Some(handle) = state.sidecars.lock().await.remove(MAIN_SERVICE) {
+                if let Err(kill_error) = handle.kill() {
+                    warn!("Failed to kill backend that lost its port: {}", kill_error);
+                }
+            }
+            tried_ports.push(port);
+            let next_port = match select_backend_port(&config, None, &tried_ports) {
+                Ok(next_port) => next_port,
+                Err(select_error) => {
+                    warn!("No port left to retry the backend on: {}", select_error);
+                    break (pid, ready);
+                }
+            };
+            let reason = e.lines().next().unwrap_or_default().to_string();
+            warn!(
+                "Backend could not bind port {} (attempt {}/{}); retrying on {}",
+                port, attempt, BIND_RETRY_ATTEMPTS, next_port
+            );
+            emit_backend_event(
+                app,
+                state,
+                "backend-port-retry",
+                serde_json::json!({
+                    "attempt": attempt,
+                    "max_attempts": BIND_RETRY_ATTEMPTS,
+                    "failed_port": port,
+                    "next_port": next_port,
+                    "reason": reason,
+                }),
+            )
+            .await;
+            attempt += 1;
+            port = next_port;
+            state.backend_port.store(port, Ordering::SeqCst);
+            emit_backend_event(app, state, "backend-port", port).await;
+        }
+    };
+    match ready {
+        Ok(()) => {
+            finish_startup_progress(app, state, StartupPhase::Ready, None).await;
+            *state.backend_ready.lock().await = true;
+            state.set_backend_state(BackendState::Ready).await;
+            info!("Backend initialization complete");
+            *state.last_startup_ms.lock().await = Some(launch_start.elapsed().as_millis() as u64);
+            if let Err(e) = update_persisted_settings(app, |s| s.last_port = Some(port)) {
+                warn!("Failed to remember backend port {}: {}", port, e);
+            }
+            if let (true, Some(pid)) = (config.detached, pid) {
+                let record = DetachedRecord {
+                    pid,
+                    port,
+                    started_at_ms: unix_time_ms(),
+                };
+                if let Err(e) = write_detached_record(app, &record) {
+                    warn!("Failed to record detached backend: {}", e);
+                }
+            }
+            emit_backend_event(app, state, "backend-ready", true).await;
+            tauri::async_runtime::spawn(monitor_backend(app.clone(), state.clone()));
+            Ok(pid)
+        }
+        Err(e) => {
+            error!("Backend failed to start: {}", e);
+            if state.startup_cancelled.load(Ordering::SeqCst) {
+                if let Some(handle) = state.sidecars.lock().await.remove(MAIN_SERVICE) {
+                    if let Err(kill_error) = handle.shutdown(Duration::ZERO).await {
+                        warn!("Failed to kill cancelled backend: {}", kill_error);
+                    }
+                }
+            }
+            let summary = e.lines().next().unwrap_or_default().to_string();
+            finish_startup_progress(app, state, StartupPhase::Failed, Some(summary)).await;
+            state.set_backend_state(BackendState::Failed).await;
+            emit_backend_event(app, state, "backend-error", e.clone()).await;
+            check_bundle_after_failure(app, state, &e);
+            Err(e)
+        }
+    }
+}
+
+/// When a production startup failure looks like damaged bundled files, verify
+/// the bundle in the background and emit `backend-bundle-damaged` with the
+/// report if anything is missing or corrupted
+fn check_bundle_after_failure(app: &tauri::AppHandle, state: &Arc<AppState>, error: &str) {
+    if is_dev_mode() || !looks_like_broken_bundle(error) {
+        return;
+    }
+    let app = app.clone();
+    let state = state.clone();
+    tauri::async_runtime::spawn(async move {
+        info!("Startup failure looks like a broken bundle; verifying bundled files");
+        match verify_installed_bundle(&app).await {
+            Ok(report) if !report.ok => {
+                error!(
+                    "Backend bundle is damaged: {} missing, {} corrupted",
+                    report.missing.len(),
+                    report.corrupted.len()
+                );
+                emit_backend_event(&app, &state, "backend-bundle-damaged", report).await;
+            }
+            Ok(report) => info!("All {} bundled files are intact", report.checked),
+            Err(e) => warn!("Could not verify backend bundle: {}", e),
+        }
+    });
+}
+
+/// Stop the backend and launch it again. Callers hold `lifecycle_lock`.
+async fn restart_sidecar(
+    app: &tauri::AppHandle,
+    state: &Arc<AppState>,
+) -> Result<Option<u32>, String> {
+    let port = state.backend_port.load(Ordering::SeqCst);
+    let grace = Duration::from_secs(state.config.lock().await.shutdown_grace_secs);
+    // Extra services depend on the main backend, so they restart with it
+    stop_extra_services(state, grace).await;
+    stop_sidecar(state).await;
+    state.restart_count.fetch_add(1, Ordering::SeqCst);
+    // The old process may still hold the port for a moment after the kill
+    if state.config.lock().await.uds_path.is_none() {
+        wait_for_port_release(app, port, Duration::from_secs(PORT_RELEASE_TIMEOUT_SECS)).await;
+    }
+    let pid = launch_backend(app, state).await?;
+    start_extra_services(app, state).await;
+    Ok(pid)
+}
+
+/// `restart_sidecar` behind a boxed future, for callers inside the monitor
+/// task: a restart spawns a new monitor, and the named `Send` type breaks
+/// that cycle for the compiler
+fn restart_sidecar_boxed<'a>(
+    app: &'a tauri::AppHandle,
+    state: &'a Arc<AppState>,
+) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Option<u32>, String>> + Send + 'a>> {
+    Box::pin(restart_sidecar(app, state))
+}
+
+/// Number of in-flight requests reported by the health endpoint, if any
+async fn fetch_active_requests(state: &AppState) -> Option<u64> {
+    let body = state
+        .http_client
+        .get(state.health_url())
+        .send()
+        .await
+        .ok()?
+        .json::<serde_json::Value>()
+        .await
+        .ok()?;
+    ["active_requests", "in_flight"]
+        .iter()
+        .find_map(|key| body.get(*key)?.as_u64())
+}
+
+/// Single health probe against the primary health URL, cached in `last_health`
+async fn sample_health(state: &AppState) -> HealthSample {
+    let start = std::time::Instant::now();
+    let config = state.config.lock().await.clone();
+    let request = state
+        .http_client
+        .request(config.health_method.clone(), state.health_url());
+    let (status, version) = match request.send().await {
+        Ok(response) if config.is_healthy_status(response.status()) => {
+            let version = response
+                .json::<serde_json::Value>()
+                .await
+                .ok()
+                .and_then(|body| body.get("version")?.as_str().map(str::to_string));
+            (HealthStatus::Healthy, version)
+        }
+        Ok(_) => (HealthStatus::Unhealthy, None),
+        Err(_) => (HealthStatus::Unreachable, None),
+    };
+    let sample = HealthSample {
+        checked_at: std::time::Instant::now(),
+        status,
+        latency_ms: start.elapsed().as_millis() as u64,
+        version,
+    };
+    *state.last_health.lock().await = Some(sample.clone());
+    sample
+}
+
+/// Whether the backend currently answers its health endpoint
+async fn probe_health(state: &AppState) -> bool {
+    sample_health(state).await.status == HealthStatus::Healthy
+}
+
+/// Health snapshot pushed by the health stream
+#[derive(Clone, serde::Serialize)]
+struct HealthSnapshot {
+    status: HealthStatus,
+    latency_ms: u64,
+    backend_state: BackendState,
+    version: Option<String>,
+    timestamp_ms: u64,
+}
+
+/// Probe the backend every `interval_ms` and emit `backend-health-snapshot`
+async fn run_health_stream(
+    app: tauri::AppHandle,
+    state: Arc<AppState>,
+    cancel: Arc<AtomicBool>,
+    interval_ms: u64,
+) {
+    while !cancel.load(Ordering::SeqCst) {
+        let sample = sample_health(&state).await;
+        // Cancelled while the probe was in flight
+        if cancel.load(Ordering::SeqCst) {
+            break;
+        }
+        let snapshot = HealthSnapshot {
+            status: sample.status,
+            latency_ms: sample.latency_ms,
+            backend_state: *state.backend_state.lock().await,
+            version: sample.version,
+            timestamp_ms: unix_time_ms(),
+        };
+        if let Err(e) = app.emit("backend-health-snapshot", snapshot) {
+            warn!("Failed to emit backend-health-snapshot event: {}", e);
+        }
+        sleep(Duration::from_millis(interval_ms)).await;
+    }
+}
+
+/// Local performance figures for the metrics panel, gathered in-process
+/// from state the app already tracks; nothing is sent anywhere
+#[derive(Clone, serde::Serialize)]
+struct BackendMetrics {
+    timestamp_ms: u64,
+    backend_state: BackendState,
+    uptime_secs: Option<u64>,
+    /// Spawn-to-ready time of the most recent successful start
+    last_startup_ms: Option<u64>,
+    restart_count: u32,
+    /// Resource use of the backend process tree, while there is one
+    process: Option<ProcessMetrics>,
+    latency: Option<LatencySummary>,
+    proxy: ProxyMetrics,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct ProcessMetrics {
+    pid: u32,
+    /// The backend and its descendants (e.g. the uvicorn reload worker)
+    process_count: usize,
+    /// Summed over the tree, since the previous sample (0 on the first one);
+    /// may exceed 100 on multi-core machines
+    cpu_percent: f32,
+    memory_bytes: u64,
+    /// Threads across the tree; `None` where the OS doesn't report them
+    num_threads: Option<usize>,
+}
+
+/// Returned by `get_backend_resource_usage`
+#[derive(Clone, serde::Serialize)]
+struct ResourceUsage {
+    cpu_percent: f32,
+    memory_bytes: u64,
+    num_threads: Option<usize>,
+    /// Descendants of the backend process, e.g. uvicorn workers
+    child_count: usize,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct ProxyMetrics {
+    requests: u64,
+    errors: u64,
+    in_flight: usize,
+    queued: usize,
+}
+
+/// Resource use of the backend process and its descendants, measured
+/// against the previous sample in `metrics_system`
+async fn sample_process_tree(state: &AppState) -> Option<ProcessMetrics> {
+    let pid = backend_pid(state).await?;
+    let mut sys = state.metrics_system.lock().await;
+    // Descendants can only be found through the full process table
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    sys.process(Pid::from_u32(pid))?;
+    let tree: Vec<u32> = std::iter::once(pid)
+        .chain(collect_descendants(&sys, pid))
+        .collect();
+    // Linux lists threads as processes too; they are counted via `tasks`
+    let processes: Vec<_> = tree
+        .iter()
+        .filter_map(|pid| sys.process(Pid::from_u32(*pid)))
+        .filter(|p| p.thread_kind().is_none())
+        .collect();
+    let threads: Vec<usize> = processes
+        .iter()
+        .filter_map(|p| Some(p.tasks()?.len().max(1)))
+        .collect();
+    Some(ProcessMetrics {
+        pid,
+        process_count: processes.len(),
+        cpu_percent: processes.iter().map(|p| p.cpu_usage()).sum(),
+        memory_bytes: processes.iter().map(|p| p.memory()).sum(),
+        num_threads: (!threads.is_empty()).then(|| threads.iter().sum()),
+    })
+}
+
+async fn collect_metrics(state: &AppState) -> BackendMetrics {
+    let process = sample_process_tree(state).await;
+    let uptime_secs = state
+        .ready_since
+        .lock()
+        .await
+        .map(|since| since.elapsed().as_secs());
+
+    BackendMetrics {
+        timestamp_ms: unix_time_ms(),
+        backend_state: *state.backend_state.lock().await,
+        uptime_secs,
+        last_startup_ms: *state.last_startup_ms.lock().await,
+        restart_count: state.restart_count.load(Ordering::SeqCst),
+        process,
+        latency: state.latency_trend.lock().await.summary(),
+        proxy: ProxyMetrics {
+            requests: state.proxy_requests.load(Ordering::SeqCst),
+            errors: state.proxy_errors.load(Ordering::SeqCst),
+            in_flight: state.proxy_limit - state.proxy_limiter.available_permits(),
+            queued: state.proxy_queued.load(Ordering::SeqCst),
+        },
+    }
+}
+
+/// CPU and memory use of the backend, summed over its descendants (uvicorn
+/// workers hold most of the memory); `None` without a backend process.
+///
+/// CPU usage is relative to the previous sample, so the first call reports 0.
+#[tauri::command]
+async fn get_backend_resource_usage(
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<Option<ResourceUsage>, String> {
+    Ok(sample_process_tree(&state)
+        .await
+        .map(|metrics| ResourceUsage {
+            cpu_percent: metrics.cpu_percent,
+            memory_bytes: metrics.memory_bytes,
+            num_threads: metrics.num_threads,
+            child_count: metrics.process_count.saturating_sub(1),
+        }))
+}
+
+async fn run_metrics_stream(
+    app: tauri::AppHandle,
+    state: Arc<AppState>,
+    cancel: Arc<AtomicBool>,
+    interval_ms: u64,
+) {
+    while !cancel.load(Ordering::SeqCst) {
+        let metrics = collect_metrics(&state).await;
+        if let Err(e) = app.emit("metrics-updated", metrics) {
+            warn!("Failed to emit metrics-updated event: {}", e);
+        }
+        sleep(Duration::from_millis(interval_ms)).await;
+    }
+}
+
+/// Add a liveness latency to the trend, emit `backend-heartbeat`, and emit
+/// `backend-degraded` when the latency has just climbed past the threshold
+async fn record_heartbeat(
+    app: &tauri::AppHandle,
+    state: &Arc<AppState>,
+    latency_ms: u64,
+    degraded_factor: u64,
+) {
+    let (summary, crossed) = {
+        let mut trend = state.latency_trend.lock().await;
+        trend.record(latency_ms);
+        let crossed = trend.check_degraded(degraded_factor);
+        (trend.summary(), crossed)
+    };
+    let heartbeat = serde_json::json!({ "latency_ms": latency_ms, "latency": summary });
+    if let Err(e) = app.emit("backend-heartbeat", heartbeat) {
+        warn!("Failed to emit backend-heartbeat event: {}", e);
+    }
+    if let Some(summary) = crossed {
+        warn!(
+            "Backend health latency is degrading: {:.0} ms on average (baseline {:.0} ms)",
+            summary.average_ms,
+            summary.baseline_ms.unwrap_or_default()
+        );
+        emit_backend_event(
+            app,
+            state,
+            "backend-degraded",
+            serde_json::json!({ "latency": summary, "factor": degraded_factor }),
+        )
+        .await;
+    }
+}
+
+enum ReconnectOutcome {
+    Recovered,
+    GaveUp,
+    /// The backend was stopped or restarted while reconnecting
+    Superseded,
+}
+
+/// Watch a ready backend and reconnect with backoff when it stops answering.
+///
+/// Exits once the backend is stopped (the lifecycle generation changes) or
+/// after handing a backend that did not recover to `handle_backend_crash`.
+async fn monitor_backend(app: tauri::AppHandle, state: Arc<AppState>) {
+    let generation = state.lifecycle_generation.load(Ordering::SeqCst);
+    let reload_mode = state.config.lock().await.uvicorn_reload;
+
+    let degraded_factor = state.config.lock().await.latency_degraded_factor;
+    let memory_limit = state.config.lock().await.memory_limit_bytes;
+    *state.latency_trend.lock().await = LatencyTrend::default();
+
+    let mut failures = 0;
+    let mut reloading_since: Option<std::time::Instant> = None;
+    loop {
+        sleep(Duration::from_millis(LIVENESS_CHECK_INTERVAL_MS)).await;
+        if state.lifecycle_generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+
+        // Reap a dev-mode child that exited on its own even while health
+        // still answers (e.g. an orphaned worker holding the port), so it
+        // doesn't linger as a zombie and the exit is reported
+        if let Some(status) = sidecar_exit_status(&state).await {
+            handle_backend_exit(
+                &app,
+                &state,
+                Some(status),
+                format!("Backend process exited ({})", status),
+            )
+            .await;
+            return;
+        }
+
+        if let Some(limit) = memory_limit {
+            if let Some(used) = enforce_memory_limit(&app, &state, limit).await {
+                handle_backend_crash(
+                    &app,
+                    &state,
+                    format!(
+                        "Backend exceeded the memory limit ({} of {} bytes)",
+                        used, limit
+                    ),
+                )
+                .await;
+                return;
+            }
+        }
+
+        let sample = sample_health(&state).await;
+        if sample.status == HealthStatus::Healthy {
+            failures = 0;
+            reloading_since = None;
+            set_backend_alive(&app, &state, true, Some(sample.latency_ms)).await;
+            record_heartbeat(&app, &state, sample.latency_ms, degraded_factor).await;
+            let stable = state
+                .ready_since
+                .lock()
+                .await
+                .is_some_and(|since| since.elapsed().as_secs() >= AUTO_RESTART_RESET_SECS);
+            if stable {
+                state.auto_restarts.store(0, Ordering::SeqCst);
+            }
+            continue;
+        }
+
+        if let Some(status) = sidecar_exit_status(&state).await {
+            handle_backend_exit(
+                &app,
+                &state,
+                Some(status),
+                format!("Backend process exited ({})", status),
+            )
+            .await;
+            return;
+        }
+        if let Some(pid) = sidecar_process_gone(&state).await {
+            handle_backend_exit(
+                &app,
+                &state,
+                None,
+                format!("Backend process {} is gone", pid),
+            )
+            .await;
+            return;
+        }
+        if reload_mode {
+            // The reloader is alive, so the worker is most likely restarting
+            let since = *reloading_since.get_or_insert_with(std::time::Instant::now);
+            if since.elapsed() < Duration::from_secs(RELOAD_GRACE_SECS) {
+                info!("Backend health unavailable while uvicorn reloads its worker");
+                continue;
+            }
+        }
+        failures += 1;
+        if failures < LIVENESS_FAILURE_THRESHOLD {
+            continue;
+        }
+        failures = 0;
+
+        warn!("Backend stopped answering health checks");
+        set_backend_alive(&app, &state, false, None).await;
+        *state.backend_ready.lock().await = false;
+        state.set_backend_state(BackendState::Reconnecting).await;
+        emit_backend_event(&app, &state, "backend-lost", serde_json::Value::Null).await;
+
+        match reconnect_backend(&app, &state, generation).await {
+            ReconnectOutcome::Recovered => {}
+            ReconnectOutcome::Superseded => return,
+            ReconnectOutcome::GaveUp => {
+                let give_up_secs = state.config.lock().await.reconnect_give_up_secs;
+                handle_backend_crash(
+                    &app,
+                    &state,
+                    format!("Backend did not recover within {} seconds", give_up_secs),
+                )
+                .await;
+                return;
+            }
+        }
+    }
+}
+
+/// Kill a backend we spawned once it and its descendants use more than
+/// `limit` bytes, emitting `backend-oom`. Returns the usage that triggered it.
+async fn enforce_memory_limit(app: &tauri::AppHandle, state: &AppState, limit: u64) -> Option<u64> {
+    let managed = state
+        .sidecars
+        .lock()
+        .await
+        .get(MAIN_SERVICE)
+        .is_some_and(ProcessHandle::is_spawned);
+    if !managed {
+        return None;
+    }
+    let before = sample_process_tree(state).await?;
+    if before.memory_bytes <= limit {
+        return None;
+    }
+
+    warn!(
+        "Backend PID {} uses {} bytes across {} process(es), over the {} byte limit; killing it",
+        before.pid, before.memory_bytes, before.process_count, limit
+    );
+    kill_process_tree(before.pid);
+    if let Some(process) = state
+        .metrics_system
+        .lock()
+        .await
+        .process(Pid::from_u32(before.pid))
+    {
+        process.kill();
+    }
+    sleep(Duration::from_millis(OOM_KILL_SETTLE_MS)).await;
+    let after = sample_process_tree(state)
+        .await
+        .map_or(0, |metrics| metrics.memory_bytes);
+    warn!(
+        "Backend memory after the kill: {} bytes (was {})",
+        after, before.memory_bytes
+    );
+
+    emit_backend_event(
+        app,
+        state,
+        "backend-oom",
+        serde_json::json!({
+            "pid": before.pid,
+            "memory_bytes": before.memory_bytes,
+            "process_count": before.process_count,
+            "limit_bytes": limit,
+        }),
+    )
+    .await;
+    Some(before.memory_bytes)
+}
+
+/// Record whether the backend answers health checks, emitting
+/// `backend-health-changed` with `{ alive, latency_ms }` when that flips
+async fn set_backend_alive(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    alive: bool,
+    latency_ms: Option<u64>,
+) {
+    let previous = state.backend_alive.lock().await.replace(alive);
+    if previous == Some(alive) {
+        return;
+    }
+    emit_backend_event(
+        app,
+        state,
+        "backend-health-changed",
+        serde_json::json!({ "alive": alive, "latency_ms": latency_ms }),
+    )
+    .await;
+}
+
+/// Retry health with exponential backoff until it answers or we give up
+async fn reconnect_backend(
+    app: &tauri::AppHandle,
+    state: &Arc<AppState>,
+    generation: u64,
+) -> ReconnectOutcome {
+    let config = state.config.lock().await.clone();
+    let lost_at = std::time::Instant::now();
+    let give_up = Duration::from_secs(config.reconnect_give_up_secs);
+    let mut delay_ms = config.reconnect_initial_ms;
+    let mut attempt: u32 = 0;
+
+    while lost_at.elapsed() < give_up {
+        attempt += 1;
+        emit_backend_event(
+            app,
+            state,
+            "backend-reconnecting",
+            serde_json::json!({ "attempt": attempt, "delay_ms": delay_ms }),
+        )
+        .await;
+        sleep(Duration::from_millis(delay_ms)).await;
+
+        if state.lifecycle_generation.load(Ordering::SeqCst) != generation {
+            return ReconnectOutcome::Superseded;
+        }
+        let sample = sample_health(state).await;
+        if sample.status == HealthStatus::Healthy {
+            set_backend_alive(app, state, true, Some(sample.latency_ms)).await;
+            let downtime_ms = lost_at.elapsed().as_millis() as u64;
+            info!(
+                "Backend recovered after {} attempt(s) ({} ms)",
+                attempt, downtime_ms
+            );
+            *state.backend_ready.lock().await = true;
+            state.set_backend_state(BackendState::Ready).await;
+            emit_backend_event(
+                app,
+                state,
+                "backend-recovered",
+                serde_json::json!({ "attempts": attempt, "downtime_ms": downtime_ms }),
+            )
+            .await;
+            return ReconnectOutcome::Recovered;
+        }
+        delay_ms = delay_ms.saturating_mul(2).min(config.reconnect_max_ms);
+    }
+
+    ReconnectOutcome::GaveUp
+}
+
+/// Payload of `backend-exited`
+#[derive(Clone, serde::Serialize)]
+struct BackendExit {
+    /// Exit code; `None` when killed by a signal or the status is unknown
+    code: Option<i32>,
+    /// Terminating signal, e.g. `SIGKILL` (Unix only)
+    signal: Option<String>,
+    log_tail: Option<String>,
+}
+
+/// Name of the signal that terminated a process, if one did
+#[cfg(unix)]
+fn exit_signal(status: &std::process::ExitStatus) -> Option<String> {
+    use std::os::unix::process::ExitStatusExt;
+    let signal = status.signal()?;
+    let name = match signal {
+        1 => "SIGHUP",
+        2 => "SIGINT",
+        6 => "SIGABRT",
+        9 => "SIGKILL",
+        11 => "SIGSEGV",
+        15 => "SIGTERM",
+        _ => return Some(format!("signal {}", signal)),
+    };
+    Some(name.to_string())
+}
+
+#[cfg(not(unix))]
+fn exit_signal(_status: &std::process::ExitStatus) -> Option<String> {
+    None
+}
+
+/// A backend that was ready has exited: emit `backend-exited` with how it
+/// ended, then handle it as a crash. (`backend-error` stays reserved for
+/// failed starts.) `status` is `None` when the process isn't our child and
+/// was only noticed to be gone.
+async fn handle_backend_exit(
+    app: &tauri::AppHandle,
+    state: &Arc<AppState>,
+    status: Option<std::process::ExitStatus>,
+    reason: String,
+) {
+    if *state.backend_ready.lock().await {
+        let exit = BackendExit {
+            code: status.and_then(|status| status.code()),
+            signal: status.as_ref().and_then(exit_signal),
+            log_tail: read_backend_error_tail(state, 80).await,
+        };
+        emit_backend_event(app, state, "backend-exited", exit).await;
+    }
+    handle_backend_crash(app, state, reason).await;
+}
+
+/// Mark the backend as crashed and notify the frontend.
+///
+/// Backends we spawned (`managed`) are restarted after the
+/// `restart_delay_ms` countdown; reattached ones are left crashed. After
+/// `max_auto_restarts` restarts without a stable run in between, the crash
+/// is reported with `backend-error` instead.
+async fn handle_backend_crash(app: &tauri::AppHandle, state: &Arc<AppState>, reason: String) {
+    let managed = state
+        .sidecars
+        .lock()
+        .await
+        .get(MAIN_SERVICE)
+        .is_some_and(ProcessHandle::is_spawned);
+    error!("Backend crashed: {}", reason);
+    set_backend_alive(app, state, false, None).await;
+    *state.backend_ready.lock().await = false;
+    state.set_backend_state(BackendState::Crashed).await;
+
+    let log_tail = read_backend_error_tail(state, 80).await;
+    emit_backend_event(
+        app,
+        state,
+        "backend-crashed",
+        serde_json::json!({ "reason": reason, "managed": managed, "log_tail": log_tail }),
+    )
+    .await;
+
+    if !managed {
+        return;
+    }
+    let max_restarts = state.config.lock().await.max_auto_restarts;
+    let attempt = state.auto_restarts.fetch_add(1, Ordering::SeqCst) + 1;
+    if attempt > max_restarts {
+        state.auto_restarts.store(0, Ordering::SeqCst);
+        let message = format!(
+            "Backend crashed {} times in a row; not restarting it again ({})\n{}",
+            max_restarts + 1,
+            reason,
+            log_tail.unwrap_or_default()
+        );
+        error!("{}", message.lines().next().unwrap_or_default());
+        state.set_backend_state(BackendState::Failed).await;
+        emit_backend_event(app, state, "backend-error", message).await;
+        return;
+    }
+    restart_after_delay(app, state, reason, attempt, max_restarts).await;
+}
+
+/// Restart a crashed backend once `restart_delay_ms` has passed.
+///
+/// `backend-will-restart` is emitted with the crash reason and the time left
+/// at the start and about once a second, so the frontend can show the error
+/// and offer `cancel_pending_restart`. A stop or restart from elsewhere
+/// during the countdown also calls it off.
+async fn restart_after_delay(
+    app: &tauri::AppHandle,
+    state: &Arc<AppState>,
+    reason: String,
+    attempt: u32,
+    max_attempts: u32,
+) {
+    let delay_ms = state.config.lock().await.restart_delay_ms;
+    let generation = state.lifecycle_generation.load(Ordering::SeqCst);
+    let cancel = Arc::new(AtomicBool::new(false));
+    *state.pending_restart.lock().await = Some(cancel.clone());
+
+    let deadline = std::time::Instant::now() + Duration::from_millis(delay_ms);
+    loop {
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        emit_backend_event(
+            app,
+            state,
+            "backend-will-restart",
+            serde_json::json!({
+                "reason": reason,
+                "delay_ms": delay_ms,
+                "remaining_ms": remaining.as_millis() as u64,
+            }),
+        )
+        .await;
+        if remaining.is_zero() {
+            break;
+        }
+        sleep(remaining.min(Duration::from_secs(1))).await;
+        if cancel.load(Ordering::SeqCst) {
+            info!("Backend restart cancelled");
+            return;
+        }
+        if state.lifecycle_generation.load(Ordering::SeqCst) != generation
+            || state.shutting_down.load(Ordering::SeqCst)
+        {
+            state.pending_restart.lock().await.take();
+            return;
+        }
+    }
+
+    let _guard = state.lifecycle_lock.lock().await;
+    {
+        let mut pending = state.pending_restart.lock().await;
+        if cancel.load(Ordering::SeqCst) {
+            return;
+        }
+        pending.take();
+    }
+    if state.lifecycle_generation.load(Ordering::SeqCst) != generation {
+        return;
+    }
+    info!(
+        "Restarting backend after crash (attempt {}/{})",
+        attempt, max_attempts
+    );
+    emit_backend_event(
+        app,
+        state,
+        "backend-restarting",
+        serde_json::json!({
+            "reason": reason,
+            "attempt": attempt,
+            "max_attempts": max_attempts,
+        }),
+    )
+    .await;
+    if let Err(e) = restart_sidecar_boxed(app, state).await {
+        error!("Backend restart after crash failed: {}", e);
+    }
+}
+
+/// Stop the sidecar process gracefully, within the configured
+/// `shutdown_grace_secs`
+async fn stop_sidecar(state: &AppState) {
+    let grace = Duration::from_secs(state.config.lock().await.shutdown_grace_secs);
+    stop_sidecar_graceful(state, grace).await;
+}
+
+/// PID of the backend we spawned or reattached to
+async fn backend_pid(state: &AppState) -> Option<u32> {
+    state
+        .sidecars
+        .lock()
+        .await
+        .get(MAIN_SERVICE)
+        .and_then(|h| h.pid())
+}
+
+/// Whether closing the app leaves the backend running: detached mode keeps
+/// it alive by design, and an attached backend is not ours to stop on close
+async fn backend_outlives_close(state: &AppState) -> bool {
+    let config = state.config.lock().await;
+    if config.detached || config.attach {
+        return true;
+    }
+    drop(config);
+    matches!(
+        state.sidecars.lock().await.get(MAIN_SERVICE),
+        Some(ProcessHandle::Attached { .. })
+    )
+}
+
+/// Ask the backend to terminate and wait up to `timeout` for it to exit,
+/// then force-kill whatever is left (see `ProcessHandle::shutdown`)
+async fn stop_sidecar_graceful(state: &AppState, timeout: Duration) {
+    state.lifecycle_generation.fetch_add(1, Ordering::SeqCst);
+    state.streams.lock().await.cancel(Some(StreamKind::Health));
+    *state.backend_ready.lock().await = false;
+    *state.backend_build_info.lock().await = None;
+    state.set_backend_state(BackendState::Stopped).await;
+
+    let handle = state.sidecars.lock().await.remove(MAIN_SERVICE);
+    if let Some(handle) = handle {
+        info!("Stopping backend sidecar...");
+        if let Err(e) = handle.shutdown(timeout).await {
+            error!("Failed to kill sidecar process: {}", e);
+        } else {
+            info!("Backend sidecar stopped");
+        }
+    }
+}
+
+/// Working directory of extra services: the backend directory in dev mode,
+/// the sidecar directory in bundled builds
+fn service_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    if is_dev_mode() {
+        return get_dev_backend_dir(app);
+    }
+    let dir_name = get_sidecar_dir_name()?;
+    sidecar_resource_roots(app)?
+        .into_iter()
+        .map(|root| root.join("binaries").join(&dir_name))
+        .find(|dir| dir.is_dir())
+        .ok_or_else(|| format!("Sidecar directory {} not found", dir_name))
+}
+
+/// Where extra services run and write their logs
+struct ServiceDirs {
+    work_dir: PathBuf,
+    log_dir: PathBuf,
+}
+
+impl ServiceDirs {
+    fn resolve(app: &tauri::AppHandle) -> Result<Self, String> {
+        Ok(Self {
+            work_dir: service_dir(app)?,
+            log_dir: resolve_backend_log_dir(app),
+        })
+    }
+}
+
+/// Spawn the extra service `name`, logging both streams to
+/// `service-<name>.log` next to the backend logs
+async fn spawn_service(
+    dirs: &ServiceDirs,
+    config: &BackendConfig,
+    name: &str,
+    service: &ServiceConfig,
+) -> Result<ProcessHandle, String> {
+    let dir = &dirs.work_dir;
+    let (program, args) = service
+        .command
+        .split_first()
+        .ok_or_else(|| format!("Service {} has no command", name))?;
+    let program = Path::new(program);
+    let program = if program.is_relative() && program.components().count() > 1 {
+        dir.join(program)
+    } else {
+        program.to_path_buf()
+    };
+
+    let log_path = dirs.log_dir.join(format!("service-{}.log", name));
+    let log = open_run_log(log_path, config, false)?;
+    let stderr = log
+        .file
+        .try_clone()
+        .map_err(|e| format!("Failed to share service log {:?}: {}", log.path, e))?;
+
+    let mut command = Command::new(&program);
+    command
+        .args(args)
+        .current_dir(dir)
+        .stdout(Stdio::from(log.file))
+        .stderr(Stdio::from(stderr));
+    configure_process_flags(&mut command, config);
+    apply_backend_env(&mut command, config);
+
+    let child = spawn_with_retry(&mut command)
+        .await
+        .map_err(|e| format!("Failed to spawn service {} ({:?}): {}", name, program, e))?;
+    info!(
+        "Service {} started with PID {} (log {:?})",
+        name,
+        child.id(),
+        log.path
+    );
+    Ok(ProcessHandle::StdChild(child))
+}
+
+/// Whether the process behind `handle` is still running
+fn service_running(handle: &mut ProcessHandle) -> bool {
+    match handle {
+        ProcessHandle::StdChild(child) => matches!(child.try_wait(), Ok(None)),
+        handle => handle.pid().map_or(true, process_exists),
+    }
+}
+
+/// Start the backend service `name` unless it is already running and
+/// return its PID. `MAIN_SERVICE` goes through `launch_backend`; a main
+/// backend whose process died or that ended up `Failed` is cleaned up and
+/// launched again, and the extra services come back with it.
+///
+/// Callers hold `lifecycle_lock`.
+async fn start_service_by_name(
+    app: &tauri::AppHandle,
+    state: &Arc<AppState>,
+    name: &str,
+) -> Result<Option<u32>, String> {
+    if name == MAIN_SERVICE {
+        let failed = *state.backend_state.lock().await == BackendState::Failed;
+        let running = state
+            .sidecars
+            .lock()
+            .await
+            .get_mut(MAIN_SERVICE)
+            .map(service_running);
+        match running {
+            Some(true) if !failed => return Ok(backend_pid(state).await),
+            Some(true) => stop_sidecar(state).await,
+            Some(false) => {
+                state.sidecars.lock().await.remove(MAIN_SERVICE);
+            }
+            None => {}
+        }
+        let pid = launch_backend(app, state).await?;
+        start_extra_services(app, state).await;
+        return Ok(pid);
+    }
+    start_extra_service(state, &ServiceDirs::resolve(app)?, name).await
+}
+
+/// Start the extra service `name` unless it is running; returns its PID
+async fn start_extra_service(
+    state: &AppState,
+    dirs: &ServiceDirs,
+    name: &str,
+) -> Result<Option<u32>, String> {
+    let config = state.config.lock().await.clone();
+    let service = config
+        .services
+        .get(name)
+        .ok_or_else(|| format!("Unknown service {:?}", name))?;
+    if let Some(handle) = state.sidecars.lock().await.get_mut(name) {
+        if service_running(handle) {
+            return Ok(handle.pid());
+        }
+    }
+    let handle = spawn_service(dirs, &config, name, service).await?;
+    let pid = handle.pid();
+    state.sidecars.lock().await.insert(name.to_string(), handle);
+    Ok(pid)
+}
+
+/// Start every configured extra service, logging the ones that fail
+async fn start_extra_services(app: &tauri::AppHandle, state: &AppState) {
+    let mut names: Vec<String> = state.config.lock().await.services.keys().cloned().collect();
+    if names.is_empty() {
+        return;
+    }
+    let dirs = match ServiceDirs::resolve(app) {
+        Ok(dirs) => dirs,
+        Err(e) => {
+            error!("Failed to start services: {}", e);
+            return;
+        }
+    };
+    names.sort();
+    for name in names {
+        if let Err(e) = start_extra_service(state, &dirs, &name).await {
+            error!("Failed to start service {}: {}", name, e);
+        }
+    }
+}
+
+/// Stop the backend service `name`, waiting up to `timeout` before
+/// force-killing it. `MAIN_SERVICE` goes through `stop_sidecar_graceful`.
+/// Returns whether there was a process to stop.
+async fn stop_service_by_name(state: &AppState, name: &str, timeout: Duration) -> bool {
+    if name == MAIN_SERVICE {
+        let running = state.sidecars.lock().await.contains_key(MAIN_SERVICE);
+        stop_sidecar_graceful(state, timeout).await;
+        return running;
+    }
+    let Some(handle) = state.sidecars.lock().await.remove(name) else {
+        return false;
+    };
+    info!("Stopping service {}...", name);
+    match handle.shutdown(timeout).await {
+        Ok(()) => info!("Service {} stopped", name),
+        Err(e) => error!("Failed to stop service {}: {}", name, e),
+    }
+    true
+}
+
+/// Stop every service except `MAIN_SERVICE`
+async fn stop_extra_services(state: &AppState, timeout: Duration) {
+    let names: Vec<String> = state
+        .sidecars
+        .lock()
+        .await
+        .keys()
+        .filter(|name| *name != MAIN_SERVICE)
+        .cloned()
+        .collect();
+    for name in names {
+        stop_service_by_name(state, &name, timeout).await;
+    }
+}
+
+/// Payload of `service-exited`
+#[derive(Clone, serde::Serialize)]
+struct ServiceExit {
+    name: String,
+    code: Option<i32>,
+}
+
+/// Watch the extra services for the life of the app. A service that exits
+/// on its own is reported with `service-exited` and started again, up to
+/// `max_auto_restarts` times within `AUTO_RESTART_RESET_SECS`. Once the main
+/// backend is `Failed` (including after it ran out of auto-restarts) the
+/// extra services are stopped; starting the backend again brings them back.
+async fn monitor_services(app: tauri::AppHandle, state: Arc<AppState>) {
+    let mut restarts: HashMap<String, (u32, std::time::Instant)> = HashMap::new();
+    loop {
+        sleep(Duration::from_millis(LIVENESS_CHECK_INTERVAL_MS)).await;
+        if state.shutting_down.load(Ordering::SeqCst) {
+            return;
+        }
+        if *state.backend_state.lock().await == BackendState::Failed {
+            let _lifecycle = state.lifecycle_lock.lock().await;
+            let extras_running = state
+                .sidecars
+                .lock()
+                .await
+                .keys()
+                .any(|name| name != MAIN_SERVICE);
+            if extras_running {
+                warn!("Backend failed; stopping the extra services");
+                let grace = state.config.lock().await.shutdown_grace_secs;
+                stop_extra_services(&state, Duration::from_secs(grace)).await;
+            }
+            continue;
+        }
+        let exited: Vec<ServiceExit> = {
+            let mut sidecars = state.sidecars.lock().await;
+            let mut names = Vec::new();
+            for (name, handle) in sidecars.iter_mut() {
+                if name != MAIN_SERVICE && !service_running(handle) {
+                    names.push(name.clone());
+                }
+            }
+            names
+                .into_iter()
+                .map(|name| {
+                    let code = match sidecars.remove(&name) {
+                        Some(ProcessHandle::StdChild(mut child)) => child
+                            .try_wait()
+                            .ok()
+                            .flatten()
+                            .and_then(|status| status.code()),
+                        _ => None,
+                    };
+                    ServiceExit { name, code }
+                })
+                .collect()
+        };
+        for exit in exited {
+            warn!("Service {} exited (code {:?})", exit.name, exit.code);
+            let name = exit.name.clone();
+            emit_backend_event(&app, &state, "service-exited", exit).await;
+
+            let max = state.config.lock().await.max_auto_restarts;
+            let (count, since) = restarts
+                .entry(name.clone())
+                .or_insert((0, std::time::Instant::now()));
+            if since.elapsed().as_secs() >= AUTO_RESTART_RESET_SECS {
+                *count = 0;
+            }
+            if *count >= max {
+                error!("Not restarting service {} after {} restarts", name, count);
+                continue;
+            }
+            *count += 1;
+            *since = std::time::Instant::now();
+
+            let _lifecycle = state.lifecycle_lock.lock().await;
+            if state.shutting_down.load(Ordering::SeqCst) {
+                return;
+            }
+            if *state.backend_state.lock().await == BackendState::Failed {
+                continue;
+            }
+            let result = match ServiceDirs::resolve(&app) {
+                Ok(dirs) => start_extra_service(&state, &dirs, &name).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                error!("Failed to restart service {}: {}", name, e);
+            }
+        }
+    }
+}
+
+/// Initialize the Tauri application
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    // Initialize logger
+    // Without RUST_LOG the logger accepts everything and `log::max_level`
+    // does the filtering, so `update_settings` can change the level live
+    if std::env::var_os("RUST_LOG").is_some() {
+        env_logger::Builder::from_env(env_logger::Env::default()).init();
+    } else {
+        env_logger::Builder::new()
+            .filter_level(log::LevelFilter::Trace)
+            .init();
+        log::set_max_level(log::LevelFilter::Info);
+    }
+
+    info!("Starting ALPROJ GUI");
+
+    tauri::Builder::default()
+        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_dialog::init())
+        .setup(|app| {
+            let config_dir = app.path().app_config_dir().ok();
+            app.manage(Arc::new(AppState::new(BackendConfig::load(
+                config_dir.as_deref(),
+            ))));
+
+            tauri::async_runtime::spawn(stop_backend_on_signal(
+                app.handle().clone(),
+                app.state::<Arc<AppState>>().inner().clone(),
+            ));
+
+            // Open devtools in debug mode
+            #[cfg(debug_assertions)]
+            {
+                if let Some(window) = app.get_webview_window("main") {
+                    window.open_devtools();
+                }
+            }
+
+            // Start Python sidecar
+            let app_handle = app.handle().clone();
+            let state = app.state::<Arc<AppState>>().inner().clone();
+
+            tauri::async_runtime::spawn(async move {
+                let persisted = load_persisted_settings(&app_handle);
+                {
+                    let mut config = state.config.lock().await;
+                    config.apply_persisted(&persisted);
+                    info!(
+                        "Backend health-check timeout: {} seconds",
+                        config.health_timeout_secs
+                    );
+                }
+                if let Some(level) = persisted.rust_log_level.as_deref() {
+                    match level.parse::<log::LevelFilter>() {
+                        Ok(filter) => set_rust_log_level(filter),
+                        Err(_) => warn!("Ignoring invalid persisted log level {:?}", level),
+                    }
+                }
+
+                // A detached backend from the last run is reattached, and an
+                // external one attached to, not cleaned up
+                let keep_running = {
+                    let config = state.config.lock().await;
+                    config.detached || config.attach
+                };
+                if is_dev_mode() && !keep_running {
+                    match get_dev_backend_dir(&app_handle) {
+                        Ok(backend_dir) => {
+                            let (app_target, ports) = {
+                                let config = state.config.lock().await;
+                                (config.uvicorn_app.clone(), config.port_candidates())
+                            };
+                            let cleaned =
+                                cleanup_stale_backend_processes(&backend_dir, &app_target, &ports);
+                            if cleaned > 0 {
+                                warn!("Cleaned up {} stale backend process(es)", cleaned);
+                                sleep(Duration::from_millis(300)).await;
+                            }
+                        }
+                        Err(e) => {
+                            warn!("Skipping stale backend cleanup: {}", e);
+                        }
+                    }
+                }
+
+                let (reap_orphans, app_target, ports) = {
+                    let config = state.config.lock().await;
+                    (
+                        config.reap_orphans && !config.detached && !config.attach,
+                        config.uvicorn_app.clone(),
+                        config.port_candidates(),
+                    )
+                };
+                if reap_orphans {
+                    let reaped = reap_orphaned_backends(&ports, &app_target);
+                    if reaped > 0 {
+                        warn!("Killed {} orphaned backend process(es)", reaped);
+                        sleep(Duration::from_millis(300)).await;
+                    }
+                }
+
+                // Held so `restart_backend` waits for this first start
+                let _lifecycle = state.lifecycle_lock.lock().await;
+                // Errors are reported to the frontend via `backend-error`
+                let _ = launch_backend(&app_handle, &state).await;
+                start_extra_services(&app_handle, &state).await;
+                tauri::async_runtime::spawn(monitor_services(app_handle.clone(), state.clone()));
+            });
+
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            // Handle window close to stop sidecar
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                let state = window.state::<Arc<AppState>>().inner().clone();
+                if !stop_backend_for_exit(&state) {
+                    // `shutdown_and_exit` is stopping the backend and exits when done
+                    api.prevent_close();
+                }
+            }
+        })
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            get_backend_status,
+            check_backend_health,
+            get_backend_log_cursor,
+            read_backend_log_chunk,
+            read_backend_log_entries,
+            open_log_reader,
+            read_next,
+            close_log_reader,
+            clear_backend_log,
+            read_backend_log_lines,
+            search_backend_log,
+            get_events_since,
+            run_backend_selftest,
+            set_health_timeout,
+            get_backend_info,
+            get_backend_uptime,
+            get_backend_config,
+            export_diagnostics,
+            get_backend_port,
+            get_backend_pid,
+            restart_backend,
+            restart_backend_graceful,
+            cancel_pending_restart,
+            cancel_backend_startup,
+            get_app_version,
+            get_capabilities,
+            get_backend_build_info,
+            get_backend_version,
+            start_log_stream,
+            stop_log_stream,
+            get_startup_log,
+            get_backend_log_tail,
+            shutdown_and_exit,
+            update_settings,
+            stop_backend,
+            start_service,
+            stop_service,
+            check_service_health,
+            list_services,
+            verify_backend_bundle,
+            start_health_stream,
+            stop_health_stream,
+            get_metrics,
+            get_backend_resource_usage,
+            start_metrics_stream,
+            stop_metrics_stream,
+            cancel_all_streams,
+            dump_state,
+            probe_port,
+            wait_for_port_free,
+            backend_request,
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Quitting from the macOS menu or dock doesn't close the window first
+            if let tauri::RunEvent::ExitRequested { .. } | tauri::RunEvent::Exit = event {
+                // Not managed yet if setup failed
+                if let Some(state) = app.try_state::<Arc<AppState>>() {
+                    stop_backend_for_exit(state.inner());
+                }
+            }
+        });
+}
+
+/// Cancel streams and stop the backend (unless it outlives the GUI) as the
+/// app quits. Runs once however the quit happens; returns false when a
+/// shutdown was already under way.
+fn stop_backend_for_exit(state: &Arc<AppState>) -> bool {
+    tauri::async_runtime::block_on(stop_backend_on_quit(state))
+}
+
+/// Async body of `stop_backend_for_exit`, for callers already on the runtime
+async fn stop_backend_on_quit(state: &Arc<AppState>) -> bool {
+    if state.shutting_down.swap(true, Ordering::SeqCst) {
+        return false;
+    }
+    state.streams.lock().await.cancel(None);
+    let grace_secs = state.config.lock().await.shutdown_grace_secs;
+    let limit = Duration::from_secs(grace_secs + EXIT_KILL_TIMEOUT_SECS);
+    // Extra services are stopped even when the main backend outlives close
+    if tokio::time::timeout(
+        limit,
+        stop_extra_services(state, Duration::from_secs(grace_secs)),
+    )
+    .await
+    .is_err()
+    {
+        warn!(
+            "Services did not stop within {} s; closing anyway",
+            limit.as_secs()
+        );
+    }
+    if backend_outlives_close(state).await {
+        info!("Leaving detached backend running");
+        return true;
+    }
+    if tokio::time::timeout(limit, stop_sidecar(state))
+        .await
+        .is_err()
+    {
+        warn!(
+            "Backend did not stop within {} s; closing anyway",
+            limit.as_secs()
+        );
+    }
+    true
+}
+
+/// Wait for Ctrl-C or SIGTERM (on Windows Ctrl-C, Ctrl-Break or the console
+/// closing) and return its name
+async fn termination_signal() -> std::io::Result<&'static str> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut terminate = signal(SignalKind::terminate())?;
+        let mut hangup = signal(SignalKind::hangup())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result.map(|_| "SIGINT"),
+            _ = terminate.recv() => Ok("SIGTERM"),
+            _ = hangup.recv() => Ok("SIGHUP"),
+        }
+    }
+    #[cfg(windows)]
+    {
+        use tokio::signal::windows::{ctrl_break, ctrl_close};
+
+        let mut brk = ctrl_break()?;
+        let mut close = ctrl_close()?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result.map(|_| "CTRL_C"),
+            _ = brk.recv() => Ok("CTRL_BREAK"),
+            _ = close.recv() => Ok("CTRL_CLOSE"),
+        }
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        tokio::signal::ctrl_c().await.map(|_| "interrupt")
+    }
+}
+
+/// Stop the backend and quit when the GUI process is interrupted or
+/// terminated, which otherwise skips every close handler
+async fn stop_backend_on_signal(app: tauri::AppHandle, state: Arc<AppState>) {
+    let name = match termination_signal().await {
+        Ok(name) => name,
+        Err(e) => {
+            warn!("Failed to install termination signal handlers: {}", e);
+            return;
+        }
+    };
+    info!("Received {}; stopping the backend", name);
+    stop_backend_on_quit(&state).await;
+    app.exit(0);
+}
+
+/// Stop the backend gracefully, then quit the app.
+///
+/// Use this instead of exiting from the frontend directly, which would skip
+/// the sidecar cleanup done on window close. Waits up to `timeout_secs`
+/// (default `shutdown_grace_secs`) before force-killing the backend.
+#[tauri::command]
+async fn shutdown_and_exit(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    timeout_secs: Option<u64>,
+) -> Result<(), String> {
+    if state.shutting_down.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    info!("Shutting down on request");
+    state.streams.lock().await.cancel(None);
+    let default_secs = state.config.lock().await.shutdown_grace_secs;
+    let timeout = Duration::from_secs(timeout_secs.unwrap_or(default_secs).min(60));
+    // Let a restart in progress finish so its new process is stopped too
+    let _lifecycle = tokio::time::timeout(timeout, state.lifecycle_lock.lock())
+        .await
+        .ok();
+    stop_extra_services(&state, timeout).await;
+    if backend_outlives_close(&state).await {
+        info!("Leaving detached backend running");
+    } else {
+        stop_sidecar_graceful(&state, timeout).await;
+    }
+    app.exit(0);
+    Ok(())
+}
+
+/// Stop the backend on explicit user request.
+///
+/// This is the only way (besides restarts) to stop a detached backend,
+/// which otherwise outlives the GUI.
+#[tauri::command]
+async fn stop_backend(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<(), String> {
+    let _lifecycle = state.lifecycle_lock.lock().await;
+    let grace = Duration::from_secs(state.config.lock().await.shutdown_grace_secs);
+    stop_extra_services(&state, grace).await;
+    stop_sidecar(&state).await;
+    remove_detached_record(&app);
+    Ok(())
+}
+
+/// State of one backend service
+#[derive(Clone, Debug, serde::Serialize)]
+struct ServiceStatus {
+    name: String,
+    pid: Option<u32>,
+    running: bool,
+    /// Health check result; `None` when it was not checked
+    healthy: Option<bool>,
+}
+
+/// Status of service `name`, checking its health when `check` is set and it
+/// is running. `MAIN_SERVICE` uses the backend health check, other services
+/// their `health_url`.
+async fn service_status(
+    state: &AppState,
+    name: &str,
+    check: bool,
+) -> Result<ServiceStatus, String> {
+    let health_url = if name == MAIN_SERVICE {
+        None
+    } else {
+        let service = state.config.lock().await.services.get(name).cloned();
+        match service {
+            Some(service) => service.health_url,
+            None if state.sidecars.lock().await.contains_key(name) => None,
+            None => return Err(format!("Unknown service {:?}", name)),
+        }
+    };
+    let (pid, running) = match state.sidecars.lock().await.get_mut(name) {
+        Some(handle) => (handle.pid(), service_running(handle)),
+        None => (None, false),
+    };
+    let healthy = if !check || !running {
+        None
+    } else if name == MAIN_SERVICE {
+        Some(probe_health(state).await)
+    } else if let Some(url) = health_url {
+        Some(
+            state
+                .service_client
+                .get(&url)
+                .send()
+                .await
+                .is_ok_and(|response| response.status().is_success()),
+        )
+    } else {
+        Some(true)
+    };
+    Ok(ServiceStatus {
+        name: name.to_string(),
+        pid,
+        running,
+        healthy,
+    })
+}
+
+/// Start a backend service (default: the main backend) unless it is
+/// already running; returns its PID
+#[tauri::command]
+async fn start_service(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    name: Option<String>,
+) -> Result<Option<u32>, String> {
+    let state = state.inner().clone();
+    let name = name.unwrap_or_else(|| MAIN_SERVICE.to_string());
+    let _lifecycle = state.lifecycle_lock.lock().await;
+    start_service_by_name(&app, &state, &name).await
+}
+
+/// Stop a backend service (default: the main backend, like `stop_backend`);
+/// returns whether it was running
+#[tauri::command]
+async fn stop_service(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    name: Option<String>,
+) -> Result<bool, String> {
+    let name = name.unwrap_or_else(|| MAIN_SERVICE.to_string());
+    let _lifecycle = state.lifecycle_lock.lock().await;
+    let grace = Duration::from_secs(state.config.lock().await.shutdown_grace_secs);
+    let stopped = stop_service_by_name(&state, &name, grace).await;
+    if name == MAIN_SERVICE {
+        remove_detached_record(&app);
+    }
+    Ok(stopped)
+}
+
+/// Check whether a backend service (default: the main backend) is running
+/// and healthy
+#[tauri::command]
+async fn check_service_health(
+    state: tauri::State<'_, Arc<AppState>>,
+    name: Option<String>,
+) -> Result<ServiceStatus, String> {
+    let name = name.unwrap_or_else(|| MAIN_SERVICE.to_string());
+    service_status(&state, &name, true).await
+}
+
+/// The main backend and every configured service, without health checks
+#[tauri::command]
+async fn list_services(
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<Vec<ServiceStatus>, String> {
+    let mut names: Vec<String> = state.config.lock().await.services.keys().cloned().collect();
+    names.sort();
+    names.insert(0, MAIN_SERVICE.to_string());
+    let mut statuses = Vec::with_capacity(names.len());
+    for name in names {
+        statuses.push(service_status(&state, &name, false).await?);
+    }
+    Ok(statuses)
+}
+
+/// Check the installed sidecar bundle (binary and `_internal`) against its
+/// manifest and list missing or corrupted files
+#[tauri::command]
+async fn verify_backend_bundle(app: tauri::AppHandle) -> Result<BundleReport, String> {
+    verify_installed_bundle(&app).await
+}
+
+/// Simple greeting command for testing
+#[tauri::command]
+fn greet(name: &str) -> String {
+    format!("Hello, {}! Welcome to ALPROJ GUI.", name)
+}
+
+/// Check if the Python backend is running (from state)
+#[tauri::command]
+async fn get_backend_status(state: tauri::State<'_, Arc<AppState>>) -> Result<String, String> {
+    let ready = *state.backend_ready.lock().await;
+    if ready {
+        Ok("connected".to_string())
+    } else if state.startup_progress.borrow().phase == StartupPhase::Failed {
+        Ok("failed".to_string())
+    } else {
+        Ok("connecting".to_string())
+    }
+}
+
+/// Health endpoint response returned by `check_backend_health`
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+struct HealthReport {
+    /// `status` from the body, `unknown` when it has none (a malformed or
+    /// foreign response shouldn't look healthy)
+    #[serde(default = "default_health_report_status")]
+    status: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    uptime_secs: Option<u64>,
+    /// Round trip of the health request, measured here
+    #[serde(default)]
+    latency_ms: u64,
+    /// Fields of the response not covered above
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+fn default_health_report_status() -> String {
+    "unknown".to_string()
+}
+
+/// Check backend health by calling the health endpoint
+#[tauri::command]
+async fn check_backend_health(
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<HealthReport, String> {
+    let client = &state.http_client;
+
+    let config = state.config.lock().await.clone();
+    let start = std::time::Instant::now();
+    let response = client
+        .request(config.health_method.clone(), state.health_url())
+        .send()
+        .await
+        .map_err(|e| format!("Health check request failed: {}", e))?;
+    let latency_ms = start.elapsed().as_millis() as u64;
+
+    if !config.is_healthy_status(response.status()) {
+        return Err(format!(
+            "Health check failed with status: {}",
+            response.status()
+        ));
+    }
+    // HEAD responses and 204s carry no body to parse
+    if config.health_method == reqwest::Method::HEAD
+        || response.status() == reqwest::StatusCode::NO_CONTENT
+    {
+        let mut extra = serde_json::Map::new();
+        extra.insert("http_status".to_string(), response.status().as_u16().into());
+        // Only the accepted status code vouches for these
+        return Ok(HealthReport {
+            status: "ok".to_string(),
+            version: None,
+            uptime_secs: None,
+            latency_ms,
+            extra,
+        });
+    }
+
+    let mut report = response
+        .json::<HealthReport>()
+        .await
+        .map_err(|e| format!("Failed to parse health check response: {}", e))?;
+    report.latency_ms = latency_ms;
+    Ok(report)
+}
+
+/// Current length of a backend log (`stream` defaults to stdout)
+#[tauri::command]
+async fn get_backend_log_cursor(
+    state: tauri::State<'_, Arc<AppState>>,
+    stream: Option<OutputStream>,
+) -> Result<usize, String> {
+    let log_path = state.log_path(stream.unwrap_or_default()).await;
+    let Some(path) = log_path else {
+        return Ok(0);
+    };
+
+    let meta = fs::metadata(&path)
+        .map_err(|e| format!("Failed to read backend log metadata {:?}: {}", path, e))?;
+    Ok(meta.len() as usize)
+}
+
+/// Read a backend log from `offset` (`stream` defaults to stdout).
+///
+/// When `offset` is past the end of the log, it was truncated or rotated:
+/// the read starts over at 0 and `rotated` is set, so the caller can clear
+/// what it shows.
+///
+/// ANSI color codes are stripped from `text` unless `strip_ansi` is false;
+/// a sequence split at the end of the read is left for the next call.
+#[tauri::command]
+async fn read_backend_log_chunk(
+    state: tauri::State<'_, Arc<AppState>>,
+    offset: usize,
+    max_bytes: Option<usize>,
+    strip_ansi: Option<bool>,
+    low_memory: Option<bool>,
+    stream: Option<OutputStream>,
+) -> Result<BackendLogChunk, String> {
+    let strip = strip_ansi.unwrap_or(true);
+    let limit = log_read_limit(max_bytes, low_memory);
+    let log_path = state.log_path(stream.unwrap_or_default()).await;
+    let Some(path) = log_path else {
+        return Ok(BackendLogChunk {
+            next_offset: offset,
+            text: String::new(),
+            applied_max_bytes: limit,
+            rotated: false,
+        });
+    };
+
+    let (start, text, consumed) = read_log_at(&path, offset, limit, strip)?;
+    Ok(BackendLogChunk {
+        next_offset: start + consumed,
+        text,
+        applied_max_bytes: limit,
+        rotated: start != offset,
+    })
+}
+
+/// Read up to `limit` bytes of the log at `path` from `offset`, or from the
+/// start when `offset` is past the end (the log was truncated or rotated).
+/// Returns the offset actually read from, the text and the number of bytes
+/// it covers.
+fn read_log_at(
+    path: &Path,
+    offset: usize,
+    limit: usize,
+    strip: bool,
+) -> Result<(usize, String, usize), String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open backend log {:?}: {}", path, e))?;
+    let file_len = file
+        .metadata()
+        .map_err(|e| format!("Failed to read backend log metadata {:?}: {}", path, e))?
+        .len() as usize;
+
+    let normalized_offset = if offset > file_len { 0 } else { offset };
+    file.seek(SeekFrom::Start(normalized_offset as u64))
+        .map_err(|e| format!("Failed to seek backend log {:?}: {}", path, e))?;
+
+    let to_read = (file_len - normalized_offset).min(limit);
+    if to_read == 0 {
+        return Ok((normalized_offset, String::new(), 0));
+    }
+
+    let (text, consumed) = read_log_chunk_text(&mut file, to_read, strip)
+        .map_err(|e| format!("Failed to read backend log {:?}: {}", path, e))?;
+    Ok((normalized_offset, text, consumed))
+}
+
+/// Read complete backend log lines from `offset`, parsed into level,
+/// timestamp and message for per-level styling and filtering.
+///
+/// Offsets, limits and `stream` work as in `read_backend_log_chunk`, so the
+/// two can share a cursor. A last line without its newline yet is left for
+/// the next call, unless it fills the whole read. Lines in neither the
+/// uvicorn nor the Python logging format come back with level `unknown`.
+#[tauri::command]
+async fn read_backend_log_entries(
+    state: tauri::State<'_, Arc<AppState>>,
+    offset: usize,
+    max_bytes: Option<usize>,
+    low_memory: Option<bool>,
+    stream: Option<OutputStream>,
+) -> Result<BackendLogEntries, String> {
+    let limit = log_read_limit(max_bytes, low_memory);
+    let log_path = state.log_path(stream.unwrap_or_default()).await;
+    let Some(path) = log_path else {
+        return Ok(BackendLogEntries {
+            next_offset: offset,
+            lines: Vec::new(),
+            applied_max_bytes: limit,
+            rotated: false,
+        });
+    };
+
+    let (start, mut text, consumed) = read_log_at(&path, offset, limit, false)?;
+    let complete = match text.rfind('\n') {
+        Some(end) => end + 1,
+        // A line longer than the whole read; a split character may hold
+        // back up to three bytes
+        None if consumed + 3 >= limit => text.len(),
+        None => 0,
+    };
+    text.truncate(complete);
+    Ok(BackendLogEntries {
+        next_offset: start + complete,
+        lines: text.lines().map(LogLine::parse).collect(),
+        applied_max_bytes: limit,
+        rotated: start != offset,
+    })
+}
+
+/// Clamp a requested log read size (default 64 KiB) to `1 KiB..=1 MiB`, or
+/// to `LOW_MEMORY_MAX_READ_BYTES` in low-memory mode
+fn log_read_limit(max_bytes: Option<usize>, low_memory: Option<bool>) -> usize {
+    let limit = max_bytes.unwrap_or(64 * 1024).clamp(1024, 1024 * 1024);
+    if low_memory.unwrap_or(false) {
+        limit.min(LOW_MEMORY_MAX_READ_BYTES)
+    } else {
+        limit
+    }
+}
+
+/// Read up to `to_read` bytes of log text from the current position.
+///
+/// The file is read through a fixed `LOG_READ_BUFFER_BYTES` buffer and each
+/// piece is decoded (and stripped) as it comes in. A split character (or
+/// escape sequence, when stripping) at the end of a piece is carried into the
+/// next one, and at the end of the read it is left for the next call; the
+/// returned byte count covers only the text returned. Both cut points are
+/// found in the raw bytes, since lossy decoding changes the length of invalid
+/// UTF-8.
+fn read_log_chunk_text(
+    file: &mut impl Read,
+    to_read: usize,
+    strip: bool,
+) -> std::io::Result<(String, usize)> {
+    let mut buffer = [0u8; LOG_READ_BUFFER_BYTES];
+    let mut text = String::new();
+    let mut consumed = 0;
+    // Raw bytes of an unfinished character or escape sequence
+    let mut carry = 0;
+    while consumed + carry < to_read {
+        let want = (to_read - consumed - carry).min(buffer.len() - carry);
+        let read = file.read(&mut buffer[carry..carry + want])?;
+        if read == 0 {
+            break;
+        }
+        let filled = carry + read;
+        let mut complete = utf8_complete_len(&buffer[..filled]);
+        if strip {
+            complete = ansi_complete_len(&buffer[..complete]);
+        }
+        let piece = String::from_utf8_lossy(&buffer[..complete]);
+        if strip {
+            text.push_str(&self::strip_ansi(&piece));
+        } else {
+            text.push_str(&piece);
+        }
+        consumed += complete;
+        buffer.copy_within(complete..filled, 0);
+        carry = filled - complete;
+    }
+    Ok((text, consumed))
+}
+
+/// Open the current backend log of `stream` (default stdout) for repeated
+/// reads with `read_next`.
+///
+/// Keeps the file open and the offset on the Rust side, so fast tailing
+/// doesn't reopen and stat the log on every call. Starts at `offset`
+/// (default 0, clamped to the file length). The reader follows a log that
+/// is rotated or replaced at the same path; open a new one if the backend
+/// moves to another log file.
+#[tauri::command]
+async fn open_log_reader(
+    state: tauri::State<'_, Arc<AppState>>,
+    offset: Option<usize>,
+    stream: Option<OutputStream>,
+) -> Result<u64, String> {
+    let path = state
+        .log_path(stream.unwrap_or_default())
+        .await
+        .ok_or_else(|| "Backend log is not available yet".to_string())?;
+    let mut readers = state.log_readers.lock().await;
+    if readers.open.len() >= MAX_LOG_READERS {
+        return Err(format!(
+            "Too many open log readers ({}); close one first",
+            MAX_LOG_READERS
+        ));
+    }
+
+    let mut file = fs::File::open(&path)
+        .map_err(|e| format!("Failed to open backend log {:?}: {}", path, e))?;
+    let file_len = file
+        .metadata()
+        .map_err(|e| format!("Failed to read backend log metadata {:?}: {}", path, e))?
+        .len() as usize;
+    let offset = offset.unwrap_or(0).min(file_len);
+    file.seek(SeekFrom::Start(offset as u64))
+        .map_err(|e| format!("Failed to seek backend log {:?}: {}", path, e))?;
+
+    readers.next_id += 1;
+    let id = readers.next_id;
+    readers.open.insert(id, LogReader { path, file, offset });
+    Ok(id)
+}
+
+/// Read the next chunk from a reader opened with `open_log_reader`.
+///
+/// Same limits, `strip_ansi` handling and `rotated` flag as
+/// `read_backend_log_chunk`. The file is only checked when there is nothing
+/// new, to notice truncation, or a rotated or replaced log at the reader's
+/// path, which the reader then reopens.
+#[tauri::command]
+async fn read_next(
+    state: tauri::State<'_, Arc<AppState>>,
+    id: u64,
+    max_bytes: Option<usize>,
+    strip_ansi: Option<bool>,
+    low_memory: Option<bool>,
+) -> Result<BackendLogChunk, String> {
+    let limit = log_read_limit(max_bytes, low_memory);
+    let mut readers = state.log_readers.lock().await;
+    let reader = readers
+        .open
+        .get_mut(&id)
+        .ok_or_else(|| format!("No open log reader with id {}", id))?;
+
+    // Part of the previous read may have been left for this one
+    reader
+        .file
+        .seek(SeekFrom::Start(reader.offset as u64))
+        .map_err(|e| format!("Failed to seek backend log {:?}: {}", reader.path, e))?;
+    let strip = strip_ansi.unwrap_or(true);
+    let (mut text, consumed) = read_log_chunk_text(&mut reader.file, limit, strip)
+        .map_err(|e| format!("Failed to read backend log {:?}: {}", reader.path, e))?;
+    reader.offset += consumed;
+
+    let mut rotated = false;
+    if consumed == 0 {
+        let file_len = reader
+            .file
+            .metadata()
+            .map_err(|e| {
+                format!(
+                    "Failed to read backend log metadata {:?}: {}",
+                    reader.path, e
+                )
+            })?
+            .len() as usize;
+        if reader.offset > file_len {
+            // Truncated in place: start over from the top
+            rotated = true;
+        } else if let Some(file) = reopen_if_replaced(&mut reader.file, &reader.path, reader.offset)
+        {
+            // Rotated or replaced: follow the path to the new file
+            reader.file = file;
+            rotated = true;
+        }
+        if rotated {
+            reader
+                .file
+                .seek(SeekFrom::Start(0))
+                .map_err(|e| format!("Failed to seek backend log {:?}: {}", reader.path, e))?;
+            let (restarted, consumed) = read_log_chunk_text(&mut reader.file, limit, strip)
+                .map_err(|e| format!("Failed to read backend log {:?}: {}", reader.path, e))?;
+            text = restarted;
+            reader.offset = consumed;
+        }
+    }
+
+    Ok(BackendLogChunk {
+        next_offset: reader.offset,
+        text,
+        applied_max_bytes: limit,
+        rotated,
+    })
+}
+
+/// The file now at `path`, if it is no longer the one `held` has open: it
+/// is shorter than `offset` or starts with different bytes, the way
+/// `run_log_stream` spots a rotated or replaced log
+fn reopen_if_replaced(held: &mut fs::File, path: &Path, offset: usize) -> Option<fs::File> {
+    let mut current = fs::File::open(path).ok()?;
+    let current_len = current.metadata().ok()?.len() as usize;
+    let mut current_head = Vec::new();
+    (&mut current)
+        .take(LOG_INDEX_HEAD_BYTES as u64)
+        .read_to_end(&mut current_head)
+        .ok()?;
+    held.seek(SeekFrom::Start(0)).ok()?;
+    let mut held_head = Vec::new();
+    held.take(LOG_INDEX_HEAD_BYTES as u64)
+        .read_to_end(&mut held_head)
+        .ok()?;
+    let shared = current_head.len().min(held_head.len());
+    if current_len >= offset && current_head[..shared] == held_head[..shared] {
+        return None;
+    }
+    current.seek(SeekFrom::Start(0)).ok()?;
+    Some(current)
+}
+
+/// Close a reader opened with `open_log_reader`; returns whether it was open
+#[tauri::command]
+async fn close_log_reader(state: tauri::State<'_, Arc<AppState>>, id: u64) -> Result<bool, String> {
+    Ok(state.log_readers.lock().await.open.remove(&id).is_some())
+}
+
+/// Truncate a backend log (`stream` defaults to stdout) to zero length and
+/// return its previous size, so the UI can reset its offset to 0.
+///
+/// The file is truncated in place rather than replaced: the sidecar's handle
+/// was opened for appending, so it keeps writing to the same (now empty)
+/// file. Open readers, the line index and live startup output are moved
+/// back to the start of the file.
+#[tauri::command]
+async fn clear_backend_log(
+    state: tauri::State<'_, Arc<AppState>>,
+    stream: Option<OutputStream>,
+) -> Result<u64, String> {
+    let stream = stream.unwrap_or_default();
+    let Some(path) = state.log_path(stream).await else {
+        return Ok(0);
+    };
+
+    let mut readers = state.log_readers.lock().await;
+    let mut index = state.log_line_index.lock().await;
+    // Appending handles can't truncate on Windows, so open for writing
+    let file = OpenOptions::new()
+        .write(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open backend log {:?}: {}", path, e))?;
+    let previous_len = file
+        .metadata()
+        .map_err(|e| format!("Failed to read backend log metadata {:?}: {}", path, e))?
+        .len();
+    file.set_len(0)
+        .map_err(|e| format!("Failed to truncate backend log {:?}: {}", path, e))?;
+
+    for reader in readers.open.values_mut() {
+        if reader.path == path {
+            reader.offset = 0;
+        }
+    }
+    if index.path.as_deref() == Some(path.as_path()) {
+        *index = LineIndex::default();
+    }
+    drop(index);
+    drop(readers);
+
+    let mut startup_log = state.startup_log.lock().await;
+    if !startup_log.complete {
+        match stream {
+            OutputStream::Stdout => startup_log.log_start = 0,
+            OutputStream::Stderr => startup_log.stderr_log_start = 0,
+        }
+    }
+    info!("Cleared backend log {:?} ({} bytes)", path, previous_len);
+    Ok(previous_len)
+}
+
+/// Find lines of a backend log (`stream`, default stdout) containing `query`.
+///
+/// `query` is literal text unless `regex` is true; `case_insensitive`
+/// defaults to false. Each match comes with `context_lines` (default
+/// `LOG_SEARCH_DEFAULT_CONTEXT`) lines before and after it. At most
+/// `max_matches` (capped at `LOG_SEARCH_MAX_MATCHES`) are returned, with
+/// `truncated` set when there were more. The log is read line by line, so
+/// large logs are fine.
+#[tauri::command]
+async fn search_backend_log(
+    state: tauri::State<'_, Arc<AppState>>,
+    query: String,
+    case_insensitive: Option<bool>,
+    regex: Option<bool>,
+    context_lines: Option<usize>,
+    max_matches: Option<usize>,
+    stream: Option<OutputStream>,
+) -> Result<LogSearchResult, String> {
+    if query.is_empty() {
+        return Err("Search query is empty".to_string());
+    }
+    let source = if regex.unwrap_or(false) {
+        query.clone()
+    } else {
+        regex::escape(&query)
+    };
+    let pattern = regex::RegexBuilder::new(&source)
+        .case_insensitive(case_insensitive.unwrap_or(false))
+        .build()
+        .map_err(|e| format!("Invalid search pattern {:?}: {}", query, e))?;
+    let context = context_lines
+        .unwrap_or(LOG_SEARCH_DEFAULT_CONTEXT)
+        .min(LOG_SEARCH_MAX_CONTEXT);
+    let max_matches = max_matches
+        .unwrap_or(LOG_SEARCH_MAX_MATCHES)
+        .clamp(1, LOG_SEARCH_MAX_MATCHES);
+
+    let Some(path) = state.log_path(stream.unwrap_or_default()).await else {
+        return Ok(LogSearchResult {
+            matches: Vec::new(),
+            truncated: false,
+        });
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let file = fs::File::open(&path)
+            .map_err(|e| format!("Failed to open backend log {:?}: {}", path, e))?;
+        search_log(
+            std::io::BufReader::new(file),
+            &pattern,
+            context,
+            max_matches,
+        )
+        .map_err(|e| format!("Failed to search backend log {:?}: {}", path, e))
+    })
+    .await
+    .map_err(|e| format!("Log search task failed: {}", e))?
+}
+
+/// Read `count` lines (at most `MAX_LOG_LINES_PER_READ`) of the backend log
+/// starting at the zero-based `start_line`, for viewers that scroll by line.
+///
+/// The start of every `LOG_INDEX_STRIDE`th line is indexed once and the
+/// index is extended incrementally as the log grows; it is rebuilt when the
+/// log is truncated or replaced. Indexing and reading run on a blocking
+/// thread. ANSI codes are stripped unless `strip_ansi` is false.
+#[tauri::command]
+async fn read_backend_log_lines(
+    state: tauri::State<'_, Arc<AppState>>,
+    start_line: usize,
+    count: usize,
+    strip_ansi: Option<bool>,
+) -> Result<BackendLogLines, String> {
+    let log_path = state.backend_log_path.lock().await.clone();
+    let Some(path) = log_path else {
+        return Ok(BackendLogLines {
+            start_line,
+            lines: Vec::new(),
+            total_lines: 0,
+        });
+    };
+
+    let state = state.inner().clone();
+    let count = count.min(MAX_LOG_LINES_PER_READ);
+    let read = move || -> Result<(usize, Vec<Vec<u8>>), String> {
+        let mut index = state.log_line_index.blocking_lock();
+        index
+            .update(&path)
+            .map_err(|e| format!("Failed to index backend log {:?}: {}", path, e))?;
+        let total_lines = index.line_count();
+        let Some((offset, skip)) = index.seek_point(start_line) else {
+            return Ok((total_lines, Vec::new()));
+        };
+        // Only what was indexed, so the lines agree with `total_lines`
+        let indexed_len = index.indexed_len;
+        drop(index);
+
+        let mut file = fs::File::open(&path)
+            .map_err(|e| format!("Failed to open backend log {:?}: {}", path, e))?;
+        file.seek(SeekFrom::Start(offset))
+            .map_err(|e| format!("Failed to seek backend log {:?}: {}", path, e))?;
+        let lines = read_lines_after(file.take(indexed_len - offset), skip, count)
+            .map_err(|e| format!("Failed to read backend log {:?}: {}", path, e))?;
+        Ok((total_lines, lines))
+    };
+    let (total_lines, lines) = tauri::async_runtime::spawn_blocking(read)
+        .await
+        .map_err(|e| format!("Log read task failed: {}", e))??;
+
+    let strip = strip_ansi.unwrap_or(true);
+    let lines = lines
+        .iter()
+        .map(|line| {
+            let line = String::from_utf8_lossy(line);
+            if strip {
+                self::strip_ansi(&line)
+            } else {
+                line.into_owned()
+            }
+        })
+        .collect();
+
+    Ok(BackendLogLines {
+        start_line,
+        lines,
+        total_lines,
+    })
+}
+
+/// Return journaled lifecycle events with an id greater than `last_id`.
+///
+/// Polling fallback for webviews that drop Tauri events; pass the
+/// `latest_id` from the previous call (or nothing on the first call).
+/// Periodic snapshot events are not included (see `EventJournal`).
+#[tauri::command]
+async fn get_events_since(
+    state: tauri::State<'_, Arc<AppState>>,
+    last_id: Option<u64>,
+) -> Result<EventsSince, String> {
+    Ok(state.event_journal.lock().await.since(last_id.unwrap_or(0)))
+}
+
+/// Port the backend is (or was last) listening on; also sent as `backend-port`
+#[tauri::command]
+fn get_backend_port(state: tauri::State<'_, Arc<AppState>>) -> u16 {
+    state.backend_port.load(Ordering::SeqCst)
+}
+
+/// PID of the running backend, or `None` when there is none (or it is the
+/// in-process mock)
+#[tauri::command]
+async fn get_backend_pid(state: tauri::State<'_, Arc<AppState>>) -> Result<Option<u32>, String> {
+    Ok(backend_pid(&state).await)
+}
+
+/// Change the health-check timeout used by the next backend startup.
+///
+/// An in-progress wait keeps its original timeout. The value is clamped to
+/// `1..=MAX_HEALTH_CHECK_TIMEOUT_SECS`, persisted for the next launch, and
+/// returned so the UI can show what was applied.
+#[tauri::command]
+async fn set_health_timeout(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    secs: u64,
+) -> Result<u64, String> {
+    let secs = secs.clamp(1, MAX_HEALTH_CHECK_TIMEOUT_SECS);
+    state.config.lock().await.health_timeout_secs = secs;
+    info!("Health-check timeout set to {} seconds", secs);
+    update_persisted_settings(&app, |settings| settings.health_timeout_secs = Some(secs))?;
+    Ok(secs)
+}
+
+/// Launch settings in effect, returned by `get_backend_config`
+#[derive(serde::Serialize)]
+struct EffectiveBackendConfig {
+    scheme: String,
+    host: String,
+    port: u16,
+    /// Port in backend URLs, when it differs from `port`
+    url_port: Option<u16>,
+    health_url: String,
+    health_timeout_secs: u64,
+    stdout_log_path: Option<PathBuf>,
+    stderr_log_path: Option<PathBuf>,
+    /// `bundled`, `venv` (dev virtualenv Python), `uv`, `python` (system
+    /// Python), `mock` or `attach`
+    launcher: &'static str,
+    uds_path: Option<PathBuf>,
+    detached: bool,
+    attach: bool,
+    uvicorn_reload: bool,
+    uvicorn_workers: u32,
+    memory_limit_bytes: Option<u64>,
+}
+
+/// The settings actually in effect, after environment overrides and saved
+/// settings were applied, for the settings screen and bug reports
+#[tauri::command]
+async fn get_backend_config(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<EffectiveBackendConfig, String> {
+    Ok(effective_backend_config(&app, &state).await)
+}
+
+async fn effective_backend_config(
+    app: &tauri::AppHandle,
+    state: &Arc<AppState>,
+) -> EffectiveBackendConfig {
+    let config = state.config.lock().await.clone();
+    let launcher = if config.mock {
+        "mock"
+    } else if config.attach {
+        "attach"
+    } else if !is_dev_mode() {
+        "bundled"
+    } else {
+        // Before the first start, the launcher that will be tried first
+        let active = *state.active_launcher.lock().await;
+        match active.unwrap_or(config.launcher) {
+            Launcher::Auto => {
+                let has_venv = get_dev_backend_dir(app)
+                    .ok()
+                    .and_then(|dir| find_dev_python(&dir))
+                    .is_some();
+                config
+                    .launcher_order
+                    .iter()
+                    .find(|launcher| **launcher != Launcher::Venv || has_venv)
+                    .map_or("auto", |launcher| launcher.name())
+            }
+            launcher => launcher.name(),
+        }
+    };
+    let log_paths = state.log_paths().await;
+    EffectiveBackendConfig {
+        scheme: state.backend_scheme.clone(),
+        host: state.backend_host.clone(),
+        port: state.backend_port.load(Ordering::SeqCst),
+        url_port: state.backend_url_port,
+        health_url: state.health_url(),
+        health_timeout_secs: config.health_timeout_secs,
+        stdout_log_path: log_paths.as_ref().map(|paths| paths.stdout.clone()),
+        stderr_log_path: log_paths.map(|paths| paths.stderr),
+        launcher,
+        uds_path: config.uds_path,
+        detached: config.detached,
+        attach: config.attach,
+        uvicorn_reload: config.uvicorn_reload,
+        uvicorn_workers: config.uvicorn_workers,
+        memory_limit_bytes: config.memory_limit_bytes,
+    }
+}
+
+/// Copy of `vars` with the values of secret-looking names replaced
+fn redact_env<'a>(
+    vars: impl IntoIterator<Item = (&'a String, &'a String)>,
+) -> std::collections::BTreeMap<String, String> {
+    vars.into_iter()
+        .map(|(key, value)| {
+            let upper = key.to_ascii_uppercase();
+            let value = if SECRET_ENV_MARKERS.iter().any(|m| upper.contains(m)) {
+                "<redacted>".to_string()
+            } else {
+                value.clone()
+            };
+            (key.clone(), value)
+        })
+        .collect()
+}
+
+/// Copy of `config` with secret-looking backend environment values redacted
+fn redacted_config(config: &BackendConfig) -> BackendConfig {
+    let mut config = config.clone();
+    config.extra_env = redact_env(&config.extra_env).into_iter().collect();
+    config.env_file_vars = redact_env(&config.env_file_vars).into_iter().collect();
+    config
+}
+
+/// Write `files` (name, contents) into a new zip archive at `path`
+fn write_zip(path: &Path, files: &[(String, Vec<u8>)]) -> Result<(), String> {
+    use std::io::Write;
+    let file = fs::File::create(path)
+        .map_err(|e| format!("Failed to create diagnostics archive {:?}: {}", path, e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in files {
+        zip.start_file(name.as_str(), options)
+            .map_err(|e| format!("Failed to add {} to {:?}: {}", name, path, e))?;
+        zip.write_all(contents)
+            .map_err(|e| format!("Failed to write {} to {:?}: {}", name, path, e))?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to finish diagnostics archive {:?}: {}", path, e))?;
+    Ok(())
+}
+
+/// Bundle what a bug report needs into a zip at a path the user picks: the
+/// backend logs (their last `DIAGNOSTICS_LOG_MAX_BYTES`), the effective
+/// config with the backend environment, system info and the recent
+/// lifecycle events.
+///
+/// Values of environment variables whose names look secret (see
+/// `SECRET_ENV_MARKERS`) are redacted. Returns the archive path, or `None`
+/// when the save dialog was cancelled.
+#[tauri::command]
+async fn export_diagnostics(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<Option<PathBuf>, String> {
+    let file_name = format!("alproj-diagnostics-{}.zip", unix_time_ms() / 1000);
+    let dialog = app
+        .dialog()
+        .file()
+        .set_file_name(file_name)
+        .add_filter("Zip archive", &["zip"]);
+    let Some(target) = tauri::async_runtime::spawn_blocking(move || dialog.blocking_save_file())
+        .await
+        .map_err(|e| format!("Save dialog failed: {}", e))?
+    else {
+        return Ok(None);
+    };
+    let path = target
+        .into_path()
+        .map_err(|e| format!("Unsupported save location: {}", e))?;
+
+    let config = state.config.lock().await.clone();
+    let alproj_env: HashMap<String, String> = std::env::vars()
+        .filter(|(key, _)| key.starts_with("ALPROJ_"))
+        .collect();
+    let config_json = serde_json::json!({
+        "effective": effective_backend_config(&app, &state).await,
+        "backend_env": redact_env(config.env_file_vars.iter().chain(&config.extra_env)),
+        "process_env": redact_env(&alproj_env),
+    });
+    let system_json = serde_json::json!({
+        "app_version": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "os_version": System::long_os_version(),
+        "kernel_version": System::kernel_version(),
+        "dev_mode": is_dev_mode(),
+        "backend_state": *state.backend_state.lock().await,
+        "exported_at_ms": unix_time_ms(),
+    });
+    let events: Vec<JournalEntry> = state
+        .event_journal
+        .lock()
+        .await
+        .entries
+        .iter()
+        .cloned()
+        .collect();
+    let to_json = |value: &serde_json::Value| serde_json::to_vec_pretty(value).unwrap_or_default();
+    let mut files = vec![
+        ("config.json".to_string(), to_json(&config_json)),
+        ("system.json".to_string(), to_json(&system_json)),
+        (
+            "events.json".to_string(),
+            serde_json::to_vec_pretty(&events).unwrap_or_default(),
+        ),
+    ];
+    if let Some(paths) = state.log_paths().await {
+        for (name, log_path) in [
+            ("backend-stdout.log", paths.stdout),
+            ("backend-stderr.log", paths.stderr),
+        ] {
+            match read_file_tail(&log_path, DIAGNOSTICS_LOG_MAX_BYTES) {
+                Ok(text) => files.push((name.to_string(), text.into_bytes())),
+                Err(e) => warn!("Leaving {:?} out of diagnostics: {}", log_path, e),
+            }
+        }
+    }
+
+    let archive = path.clone();
+    tauri::async_runtime::spawn_blocking(move || write_zip(&archive, &files))
+        .await
+        .map_err(|e| format!("Diagnostics export task failed: {}", e))??;
+    info!("Exported diagnostics to {:?}", path);
+    Ok(Some(path))
+}
+
+#[derive(serde::Serialize)]
+struct BackendInfo {
+    state: BackendState,
+    /// Whether the GUI spawned (and owns) the backend process
+    is_managed: bool,
+    pid: Option<u32>,
+    endpoint: String,
+    version: Option<String>,
+    uptime_secs: Option<u64>,
+    /// When the backend became ready, in ms since the Unix epoch
+    ready_at_ms: Option<u64>,
+    last_health_latency_ms: Option<u64>,
+    restart_count: u32,
+    /// `backend_request` calls currently running / waiting for a permit
+    proxy_in_flight: usize,
+    proxy_queued: usize,
+    /// Progress of the most recent start
+    startup_progress: StartupProgress,
+    /// Liveness latency trend since the backend became ready
+    latency_trend: Option<LatencySummary>,
+}
+
+/// Seconds since the backend last became ready, `None` while it is not.
+///
+/// Restarts start the count over; reconnecting after a brief health outage
+/// does not.
+#[tauri::command]
+async fn get_backend_uptime(state: tauri::State<'_, Arc<AppState>>) -> Result<Option<u64>, String> {
+    Ok(state
+        .ready_since
+        .lock()
+        .await
+        .map(|since| since.elapsed().as_secs()))
+}
+
+/// Everything the status panel needs in one call.
+///
+/// Uses the cached health result and only probes the backend again when the
+/// cache is older than `HEALTH_INFO_STALE_SECS` and the backend should be up.
+#[tauri::command]
+async fn get_backend_info(state: tauri::State<'_, Arc<AppState>>) -> Result<BackendInfo, String> {
+    let backend_state = *state.backend_state.lock().await;
+    let stale = state
+        .last_health
+        .lock()
+        .await
+        .as_ref()
+        .map(|sample| sample.checked_at.elapsed() > Duration::from_secs(HEALTH_INFO_STALE_SECS))
+        .unwrap_or(true);
+    if stale && backend_state == BackendState::Ready {
+        probe_health(&state).await;
+    }
+
+    let is_managed = state
+        .sidecars
+        .lock()
+        .await
+        .get(MAIN_SERVICE)
+        .is_some_and(ProcessHandle::is_spawned);
+    let pid = backend_pid(&state).await;
+    let last_health = state.last_health.lock().await.clone();
+    let uptime_secs = state
+        .ready_since
+        .lock()
+        .await
+        .map(|since| since.elapsed().as_secs());
+    let latency_trend = state.latency_trend.lock().await.summary();
+
+    Ok(BackendInfo {
+        state: backend_state,
+        is_managed,
+        pid,
+        endpoint: state.backend_base_url(),
+        version: last_health.as_ref().and_then(|s| s.version.clone()),
+        uptime_secs,
+        ready_at_ms: *state.ready_at_ms.lock().await,
+        last_health_latency_ms: last_health
+            .as_ref()
+            .filter(|s| s.status == HealthStatus::Healthy)
+            .map(|s| s.latency_ms),
+        restart_count: state.restart_count.load(Ordering::SeqCst),
+        proxy_in_flight: state.proxy_limit - state.proxy_limiter.available_permits(),
+        proxy_queued: state.proxy_queued.load(Ordering::SeqCst),
+        startup_progress: state.startup_progress.borrow().clone(),
+        latency_trend,
+    })
+}
+
+/// Internal state snapshot returned by `dump_state`. Fields behind a lock
+/// that stayed busy are `None` and the lock is listed in `busy_locks`.
+#[derive(serde::Serialize)]
+struct StateDump {
+    backend_state: Option<BackendState>,
+    backend_ready: Option<bool>,
+    /// Variant of the spawned process handle, if there is one
+    handle_kind: Option<Option<&'static str>>,
+    handle_pid: Option<Option<u32>>,
+    backend_port: u16,
+    log_path: Option<Option<PathBuf>>,
+    stderr_log_path: Option<Option<PathBuf>>,
+    lifecycle_generation: u64,
+    restart_count: u32,
+    lifecycle_lock_held: bool,
+    pending_restart: Option<bool>,
+    shutting_down: bool,
+    startup_progress: StartupProgress,
+    last_health: Option<Option<HealthDump>>,
+    streams: Option<Vec<(u64, StreamKind)>>,
+    log_readers: Option<Vec<u64>>,
+    proxy_in_flight: usize,
+    proxy_queued: usize,
+    /// `BackendConfig` in its `Debug` form, with secrets redacted
+    config: Option<String>,
+    busy_locks: Vec<&'static str>,
+}
+
+#[derive(serde::Serialize)]
+struct HealthDump {
+    status: HealthStatus,
+    latency_ms: u64,
+    age_ms: u64,
+}
+
+/// Read through `mutex`, or record `name` as busy when it isn't free within
+/// `DUMP_LOCK_TIMEOUT_MS`, so a dump never hangs on a stuck task's lock
+async fn read_for_dump<T, R>(
+    mutex: &Mutex<T>,
+    name: &'static str,
+    busy: &mut Vec<&'static str>,
+    read: impl FnOnce(&T) -> R,
+) -> Option<R> {
+    match tokio::time::timeout(Duration::from_millis(DUMP_LOCK_TIMEOUT_MS), mutex.lock()).await {
+        Ok(guard) => Some(read(&guard)),
+        Err(_) => {
+            busy.push(name);
+            None
+        }
+    }
+}
+
+/// Dump the internal state in one call, for diagnosing a stuck app.
+///
+/// Available in dev builds, or in release builds started with
+/// `ALPROJ_DIAGNOSTICS=1`. Secrets in the backend environment are redacted
+/// as in `export_diagnostics`. Every lock is taken one at a time with a timeout,
+/// so this works even while another task holds one of them.
+#[tauri::command]
+async fn dump_state(state: tauri::State<'_, Arc<AppState>>) -> Result<StateDump, String> {
+    if !is_dev_mode() && env_flag(ENV_DIAGNOSTICS) != Some(true) {
+        return Err(format!(
+            "dump_state is only available in dev builds or with {}=1",
+            ENV_DIAGNOSTICS
+        ));
+    }
+
+    let mut busy = Vec::new();
+    let backend_state =
+        read_for_dump(&state.backend_state, "backend_state", &mut busy, |s| *s).await;
+    let backend_ready =
+        read_for_dump(&state.backend_ready, "backend_ready", &mut busy, |r| *r).await;
+    let handle = read_for_dump(&state.sidecars, "sidecars", &mut busy, |h| {
+        h.get(MAIN_SERVICE).map(|h| (h.kind(), h.pid()))
+    })
+    .await;
+    let log_path = read_for_dump(
+        &state.backend_log_path,
+        "backend_log_path",
+        &mut busy,
+        |p| p.clone(),
+    )
+    .await;
+    let stderr_log_path = read_for_dump(
+        &state.backend_stderr_log_path,
+        "backend_stderr_log_path",
+        &mut busy,
+        |p| p.clone(),
+    )
+    .await;
+    let pending_restart =
+        read_for_dump(&state.pending_restart, "pending_restart", &mut busy, |p| {
+            p.is_some()
+        })
+        .await;
+    let last_health = read_for_dump(&state.last_health, "last_health", &mut busy, |h| {
+        h.as_ref().map(|sample| HealthDump {
+            status: sample.status,
+            latency_ms: sample.latency_ms,
+            age_ms: sample.checked_at.elapsed().as_millis() as u64,
+        })
+    })
+    .await;
+    let streams = read_for_dump(&state.streams, "streams", &mut busy, |streams| {
+        let mut active: Vec<_> = streams
+            .active
+            .iter()
+            .map(|(id, (kind, _))| (*id, *kind))
+            .collect();
+        active.sort_by_key(|(id, _)| *id);
+        active
+    })
+    .await;
+    let log_readers = read_for_dump(&state.log_readers, "log_readers", &mut busy, |readers| {
+        let mut ids: Vec<_> = readers.open.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    })
+    .await;
+    let config = read_for_dump(&state.config, "config", &mut busy, |c| {
+        format!("{:#?}", redacted_config(c))
+    })
+    .await;
+    let startup_progress = state.startup_progress.borrow().clone();
+
+    Ok(StateDump {
+        backend_state,
+        backend_ready,
+        handle_kind: handle.map(|h| h.map(|(kind, _)| kind)),
+        handle_pid: handle.map(|h| h.and_then(|(_, pid)| pid)),
+        backend_port: state.backend_port.load(Ordering::SeqCst),
+        log_path,
+        stderr_log_path,
+        lifecycle_generation: state.lifecycle_generation.load(Ordering::SeqCst),
+        restart_count: state.restart_count.load(Ordering::SeqCst),
+        lifecycle_lock_held: state.lifecycle_lock.try_lock().is_err(),
+        pending_restart,
+        shutting_down: state.shutting_down.load(Ordering::SeqCst),
+        startup_progress,
+        last_health,
+        streams,
+        log_readers,
+        proxy_in_flight: state.proxy_limit - state.proxy_limiter.available_permits(),
+        proxy_queued: state.proxy_queued.load(Ordering::SeqCst),
+        config,
+        busy_locks: busy,
+    })
+}
+
+/// Settings changed from the UI; omitted fields stay as they are
+#[derive(Debug, Default, serde::Deserialize)]
+struct SettingsUpdate {
+    port: Option<u16>,
+    health_timeout_secs: Option<u64>,
+    backend_log_level: Option<String>,
+    /// Replaces the whole extra backend environment
+    env: Option<HashMap<String, String>>,
+    rust_log_level: Option<String>,
+}
+
+/// What `update_settings` changed, also emitted as `backend-config-changed`
+#[derive(Clone, Debug, serde::Serialize)]
+struct ConfigChange {
+    changed: Vec<String>,
+    /// Whether a changed field only takes effect after a backend restart
+    restart_required: bool,
+    restarted: bool,
+    restart_error: Option<String>,
+}
+
+const BACKEND_LOG_LEVELS: &[&str] = &["DEBUG", "INFO", "WARNING", "ERROR", "CRITICAL"];
+
+fn set_rust_log_level(filter: log::LevelFilter) {
+    // With RUST_LOG set, env_logger keeps its own filter and this can only lower verbosity
+    log::set_max_level(filter);
+}
+
+/// Apply changed settings, restarting the backend only when needed.
+///
+/// Port, backend log level and environment require a restart, which happens
+/// right away unless `restart` is false (the change then applies on the next
+/// start). The health timeout and the shell's log level apply live. Only the
+/// changed settings are persisted for the next launch, where an `ALPROJ_*`
+/// environment override still wins over them.
+#[tauri::command]
+async fn update_settings(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    settings: SettingsUpdate,
+    restart: Option<bool>,
+) -> Result<ConfigChange, String> {
+    if settings.port == Some(0) {
+        return Err("Port must be between 1 and 65535".to_string());
+    }
+    let backend_log_level = settings
+        .backend_log_level
+        .map(|level| level.to_ascii_uppercase())
+        .map(|level| {
+            if BACKEND_LOG_LEVELS.contains(&level.as_str()) {
+                Ok(level)
+            } else {
+                Err(format!(
+                    "Invalid backend log level {:?} (expected one of {})",
+                    level,
+                    BACKEND_LOG_LEVELS.join(", ")
+                ))
+            }
+        })
+        .transpose()?;
+    let rust_log_level = settings
+        .rust_log_level
+        .map(|level| {
+            level
+                .parse::<log::LevelFilter>()
+                .map_err(|_| format!("Invalid log level {:?}", level))
+        })
+        .transpose()?;
+    if let Some(env) = &settings.env {
+        if let Some(key) = env
+            .keys()
+            .find(|key| key.is_empty() || key.contains(['=', '\0']))
+        {
+            return Err(format!("Invalid environment variable name {:?}", key));
+        }
+        if let Some(key) = env.keys().find(|key| is_reserved_backend_env(key)) {
+            return Err(format!(
+                "{} is set by the app and cannot be overridden (reserved: {})",
+                key,
+                RESERVED_BACKEND_ENV.join(", ")
+            ));
+        }
+    }
+
+    let mut changed = Vec::new();
+    let mut restart_required = false;
+    {
+        let mut config = state.config.lock().await;
+        if let Some(port) = settings.port.filter(|port| *port != config.port) {
+            config.port = port;
+            changed.push("port".to_string());
+            restart_required = true;
+        }
+        if let Some(level) =
+            backend_log_level.filter(|level| config.backend_log_level.as_ref() != Some(level))
+        {
+            config.backend_log_level = Some(level);
+            changed.push("backend_log_level".to_string());
+            restart_required = true;
+        }
+        if let Some(env) = settings.env.filter(|env| *env != config.extra_env) {
+            config.extra_env = env;
+            changed.push("env".to_string());
+            restart_required = true;
+        }
+        if let Some(secs) = settings.health_timeout_secs {
+            let secs = secs.clamp(1, MAX_HEALTH_CHECK_TIMEOUT_SECS);
+            if secs != config.health_timeout_secs {
+                config.health_timeout_secs = secs;
+                changed.push("health_timeout_secs".to_string());
+            }
+        }
+    }
+    if let Some(filter) = rust_log_level.filter(|filter| *filter != log::max_level()) {
+        set_rust_log_level(filter);
+        changed.push("rust_log_level".to_string());
+    }
+
+    if !changed.is_empty() {
+        info!("Settings changed: {}", changed.join(", "));
+        let config = state.config.lock().await.clone();
+        update_persisted_settings(&app, |persisted| {
+            for field in &changed {
+                match field.as_str() {
+                    "port" => {
+                        persisted.port = Some(config.port);
+                        // Otherwise the next start would prefer the old port
+                        persisted.last_port = None;
+                    }
+                    "health_timeout_secs" => {
+                        persisted.health_timeout_secs = Some(config.health_timeout_secs)
+                    }
+                    "backend_log_level" => {
+                        persisted.backend_log_level = config.backend_log_level.clone()
+                    }
+                    "env" => persisted.extra_env = Some(config.extra_env.clone()),
+                    "rust_log_level" => {
+                        persisted.rust_log_level = Some(log::max_level().to_string())
+                    }
+                    _ => {}
+                }
+            }
+        })?;
+    }
+
+    let mut restarted = false;
+    let mut restart_error = None;
+    if restart_required && restart.unwrap_or(true) {
+        let state = state.inner().clone();
+        let _guard = state.lifecycle_lock.lock().await;
+        match restart_sidecar(&app, &state).await {
+            Ok(_) => restarted = true,
+            Err(e) => restart_error = Some(e),
+        }
+    }
+
+    let change = ConfigChange {
+        changed,
+        restart_required,
+        restarted,
+        restart_error,
+    };
+    if !change.changed.is_empty() {
+        emit_backend_event(&app, &state, "backend-config-changed", change.clone()).await;
+    }
+    Ok(change)
+}
+
+/// Stop the backend and start it again, e.g. after editing Python code.
+///
+/// Waits for a start or restart already in progress to finish first.
+/// Emits `backend-ready` or `backend-error` like the initial launch and
+/// returns the new backend PID.
+#[tauri::command]
+async fn restart_backend(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<Option<u32>, String> {
+    let state = state.inner().clone();
+    let _guard = state.lifecycle_lock.lock().await;
+    info!("Restarting backend on request");
+    // A deliberate restart is not a crash; give automatic restarts a clean slate
+    state.auto_restarts.store(0, Ordering::SeqCst);
+    restart_sidecar(&app, &state).await
+}
+
+/// Restart the backend after letting in-flight requests finish.
+///
+/// Asks the backend to stop accepting work (`POST /api/drain`, optional) and
+/// polls the `active_requests` count from the health body until it reaches
+/// zero, emitting `backend-draining` meanwhile. Restarts immediately when the
+/// backend does not report a count, and anyway once `timeout_secs` elapses.
+#[tauri::command]
+async fn restart_backend_graceful(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    timeout_secs: Option<u64>,
+) -> Result<Option<u32>, String> {
+    let state = state.inner().clone();
+    let _guard = state.lifecycle_lock.lock().await;
+    let timeout_secs = timeout_secs.unwrap_or(DEFAULT_DRAIN_TIMEOUT_SECS);
+
+    if let Err(e) = state
+        .http_client
+        .post(format!("{}{}", state.backend_base_url(), DRAIN_PATH))
+        .send()
+        .await
+    {
+        warn!("Failed to ask backend to drain: {}", e);
+    }
+
+    let start = std::time::Instant::now();
+    loop {
+        let Some(active) = fetch_active_requests(&state).await else {
+            warn!("Backend does not report active requests; restarting without draining");
+            break;
+        };
+        if active == 0 {
+            info!("Backend drained after {} ms", start.elapsed().as_millis());
+            break;
+        }
+        if start.elapsed() >= Duration::from_secs(timeout_secs) {
+            warn!(
+                "Backend still has {} active request(s) after {} seconds; forcing restart",
+                active, timeout_secs
+            );
+            break;
+        }
+        emit_backend_event(
+            &app,
+            &state,
+            "backend-draining",
+            serde_json::json!({
+                "active_requests": active,
+                "elapsed_ms": start.elapsed().as_millis() as u64,
+                "timeout_secs": timeout_secs,
+            }),
+        )
+        .await;
+        sleep(Duration::from_millis(DRAIN_POLL_INTERVAL_MS)).await;
+    }
+
+    restart_sidecar(&app, &state).await
+}
+
+/// Call off the restart countdown that follows a backend crash.
+///
+/// Returns whether a restart was pending. The backend stays crashed until
+/// restarted by hand.
+#[tauri::command]
+async fn cancel_pending_restart(state: tauri::State<'_, Arc<AppState>>) -> Result<bool, String> {
+    let Some(cancel) = state.pending_restart.lock().await.take() else {
+        return Ok(false);
+    };
+    cancel.store(true, Ordering::SeqCst);
+    info!("Pending backend restart cancelled on request");
+    Ok(true)
+}
+
+/// Stop waiting for a backend that is still starting: the health wait gives
+/// up at its next poll, the spawned process is killed and `backend-error`
+/// reports the cancellation.
+///
+/// Returns whether a startup was in progress.
+#[tauri::command]
+async fn cancel_backend_startup(state: tauri::State<'_, Arc<AppState>>) -> Result<bool, String> {
+    if *state.backend_state.lock().await != BackendState::Starting {
+        return Ok(false);
+    }
+    state.startup_cancelled.store(true, Ordering::SeqCst);
+    info!("Backend startup cancelled on request");
+    Ok(true)
+}
+
+/// Version of the GUI itself
+#[tauri::command]
+fn get_app_version(app: tauri::AppHandle) -> String {
+    app.package_info().version.to_string()
+}
+
+/// What this build and its current configuration support
+#[derive(serde::Serialize)]
+struct Capabilities {
+    app_version: String,
+    /// `std::env::consts::OS`, e.g. `windows`, `macos`, `linux`
+    platform: &'static str,
+    arch: &'static str,
+    dev_mode: bool,
+    /// How the backend is launched now: `dev` (uvicorn), `bundled` or `mock`
+    backend_mode: &'static str,
+    /// Launch modes this build can be configured for
+    supported_backend_modes: Vec<&'static str>,
+    /// The backend outlives the GUI and is reattached (`ALPROJ_BACKEND_DETACHED`)
+    detached: bool,
+    /// How the backend is reached: `tcp` or `uds`
+    transport: &'static str,
+    /// Whether this platform can use `ALPROJ_BACKEND_UDS`
+    uds_supported: bool,
+    /// The backend writes logs the GUI can read through `start_log_stream`,
+    /// `open_log_reader` and the other log commands; not for the mock or a
+    /// reattached backend
+    backend_logs: bool,
+    /// `get_metrics` / `start_metrics_stream` include CPU and memory use;
+    /// the mock runs in-process and has none
+    process_metrics: bool,
+    /// A bundle manifest can be checked with `verify_backend_bundle`
+    bundle_verification: bool,
+    /// The backend is asked to exit (SIGTERM) before being killed
+    graceful_stop: bool,
+    /// Assets are served from the bundle rather than a dev server
+    custom_protocol: bool,
+    devtools: bool,
+}
+
+/// Describe the build so one frontend can adapt to differently configured
+/// builds; combines `cfg!` flags with the runtime configuration
+#[tauri::command]
+async fn get_capabilities(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<Capabilities, String> {
+    let config = state.config.lock().await.clone();
+    let dev_mode = is_dev_mode();
+    let launch_mode = if dev_mode { "dev" } else { "bundled" };
+    let attached = state
+        .sidecars
+        .lock()
+        .await
+        .get(MAIN_SERVICE)
+        .is_some_and(|handle| !handle.is_spawned());
+    let bundle_verification = !dev_mode
+        && get_sidecar_dir_name().is_ok_and(|dir_name| {
+            sidecar_resource_roots(&app)
+                .unwrap_or_default()
+                .iter()
+                .any(|root| {
+                    root.join("binaries")
+                        .join(&dir_name)
+                        .join(BUNDLE_MANIFEST_FILE_NAME)
+                        .is_file()
+                })
+        });
+    Ok(Capabilities {
+        app_version: app.package_info().version.to_string(),
+        platform: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        dev_mode,
+        backend_mode: if config.mock { "mock" } else { launch_mode },
+        supported_backend_modes: vec![launch_mode, "mock"],
+        detached: config.detached,
+        transport: if config.uds_path.is_some() {
+            "uds"
+        } else {
+            "tcp"
+        },
+        uds_supported: cfg!(unix),
+        backend_logs: !config.mock && !attached,
+        process_metrics: !config.mock,
+        bundle_verification,
+        graceful_stop: cfg!(unix),
+        custom_protocol: cfg!(feature = "custom-protocol"),
+        devtools: cfg!(debug_assertions),
+    })
+}
+
+/// Build metadata of the running backend, fetched once and cached.
+///
+/// Reads `/api/version` and falls back to the health body when that endpoint
+/// is missing. Pair with `get_app_version` for a full build fingerprint.
+#[tauri::command]
+async fn get_backend_build_info(
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<BackendBuildInfo, String> {
+    fetch_backend_build_info(&state).await
+}
+
+/// Version string of the running backend, from the same cached lookup as
+/// `get_backend_build_info`; `"unknown"` when the backend reports none
+#[tauri::command]
+async fn get_backend_version(state: tauri::State<'_, Arc<AppState>>) -> Result<String, String> {
+    let info = fetch_backend_build_info(&state).await?;
+    Ok(info.version.unwrap_or_else(|| "unknown".to_string()))
+}
+
+async fn fetch_backend_build_info(state: &Arc<AppState>) -> Result<BackendBuildInfo, String> {
+    if let Some(info) = state.backend_build_info.lock().await.clone() {
+        return Ok(info);
+    }
+
+    let mut body = None;
+    for url in [
+        format!("{}{}", state.backend_base_url(), VERSION_PATH),
+        state.health_url(),
+    ] {
+        match state.http_client.get(&url).send().await {
+            Ok(response) if response.status().is_success() => {
+                body = response.json::<serde_json::Value>().await.ok();
+                if body.is_some() {
+                    break;
+                }
+            }
+            Ok(response) => info!("No build info at {}: {}", url, response.status()),
+            Err(e) => warn!("Failed to fetch build info from {}: {}", url, e),
+        }
+    }
+
+    let body = body.ok_or_else(|| "Backend did not return build information".to_string())?;
+    let info = BackendBuildInfo::from_json(&body);
+    // Only a ready backend is the one that will keep running
+    if *state.backend_ready.lock().await {
+        *state.backend_build_info.lock().await = Some(info.clone());
+    }
+    Ok(info)
+}
+
+/// Formatted tail of a backend log for error dialogs, as embedded in
+/// `backend-error` messages: the last `max_lines` (default 80) lines,
+/// trimmed to a few thousand characters.
+///
+/// Without `stream` it picks the log errors go to, like startup failures
+/// do. Doesn't move any reader or cursor. `None` before the backend has
+/// written a log.
+#[tauri::command]
+async fn get_backend_log_tail(
+    state: tauri::State<'_, Arc<AppState>>,
+    max_lines: Option<usize>,
+    stream: Option<OutputStream>,
+) -> Result<Option<String>, String> {
+    let max_lines = max_lines.unwrap_or(80).clamp(1, MAX_LOG_LINES_PER_READ);
+    Ok(match stream {
+        Some(stream) => read_backend_log_tail(&state, stream, max_lines).await,
+        None => read_backend_error_tail(&state, max_lines).await,
+    })
+}
+
+/// Backend output from the most recent start, between spawn and readiness.
+///
+/// Replaced on every (re)start. While the backend is still starting the
+/// text is read live and `complete` is false.
+#[tauri::command]
+async fn get_startup_log(state: tauri::State<'_, Arc<AppState>>) -> Result<StartupLog, String> {
+    let startup_log = state.startup_log.lock().await;
+    if startup_log.complete {
+        return Ok(startup_log.clone());
+    }
+    Ok(startup_log.snapshot())
+}
+
+/// Push new output of one backend log (`stream`, default stdout) as
+/// `backend-log-appended` events.
+///
+/// Output is coalesced into at most one event per `interval_ms` (default
+/// 100 ms). If more than `LOG_STREAM_MAX_BATCH_BYTES` piles up between
+/// events, older bytes are skipped and reported in `skipped_bytes`; they
+/// can still be fetched with `read_backend_log_chunk`. Starting a stream
+/// replaces any running one for the same `stream`; stdout and stderr can be
+/// streamed side by side. Streams from the current end of the log unless
+/// `from_offset` is given. When the log is truncated, rotated or replaced the
+/// stream restarts at the top of the new file and flags the event `rotated`.
+/// ANSI color codes are stripped unless `strip_ansi` is false.
+/// Returns the stream id.
+#[tauri::command]
+async fn start_log_stream(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    interval_ms: Option<u64>,
+    from_offset: Option<usize>,
+    stream: Option<OutputStream>,
+    strip_ansi: Option<bool>,
+) -> Result<u64, String> {
+    let interval_ms = interval_ms
+        .unwrap_or(LOG_STREAM_DEFAULT_INTERVAL_MS)
+        .clamp(20, 5000);
+    let stream = stream.unwrap_or_default();
+    let offset = match from_offset {
+        Some(offset) => offset,
+        None => get_backend_log_cursor(state.clone(), Some(stream))
+            .await
+            .unwrap_or(0),
+    };
+
+    let (id, cancel) = state.streams.lock().await.start(StreamKind::Log(stream));
+    spawn_stream(
+        state.inner().clone(),
+        id,
+        run_log_stream(
+            app,
+            state.inner().clone(),
+            cancel,
+            stream,
+            interval_ms,
+            offset,
+            strip_ansi.unwrap_or(true),
+        ),
+    );
+    Ok(id)
+}
+
+/// Stop the log stream of `stream` started by `start_log_stream`, or both
+/// log streams when `stream` is not given
+#[tauri::command]
+async fn stop_log_stream(
+    state: tauri::State<'_, Arc<AppState>>,
+    stream: Option<OutputStream>,
+) -> Result<(), String> {
+    let mut streams = state.streams.lock().await;
+    match stream {
+        Some(stream) => {
+            streams.cancel(Some(StreamKind::Log(stream)));
+        }
+        None => {
+            streams.cancel(Some(StreamKind::Log(OutputStream::Stdout)));
+            streams.cancel(Some(StreamKind::Log(OutputStream::Stderr)));
+        }
+    }
+    Ok(())
+}
+
+/// Push a `backend-health-snapshot` event every `interval_ms` (default
+/// 1000 ms). Starting a stream replaces any running one; the stream also
+/// stops when the backend is stopped or restarted. Returns the stream id.
+#[tauri::command]
+async fn start_health_stream(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    interval_ms: Option<u64>,
+) -> Result<u64, String> {
+    let interval_ms = interval_ms
+        .unwrap_or(HEALTH_STREAM_DEFAULT_INTERVAL_MS)
+        .clamp(250, 60_000);
+
+    let (id, cancel) = state.streams.lock().await.start(StreamKind::Health);
+    spawn_stream(
+        state.inner().clone(),
+        id,
+        run_health_stream(app, state.inner().clone(), cancel, interval_ms),
+    );
+    Ok(id)
+}
+
+/// Stop the health stream started by `start_health_stream`
+#[tauri::command]
+async fn stop_health_stream(state: tauri::State<'_, Arc<AppState>>) -> Result<(), String> {
+    state.streams.lock().await.cancel(Some(StreamKind::Health));
+    Ok(())
+}
+
+/// Snapshot of local backend metrics: startup time, restarts, CPU and
+/// memory of the backend processes, health latency and proxy traffic
+#[tauri::command]
+async fn get_metrics(state: tauri::State<'_, Arc<AppState>>) -> Result<BackendMetrics, String> {
+    Ok(collect_metrics(&state).await)
+}
+
+/// Push a `metrics-updated` event every `interval_ms` (default
+/// `metrics_interval_ms`). Unlike the health stream it keeps running across
+/// restarts, since restarts are part of what it reports. Returns the stream id.
+#[tauri::command]
+async fn start_metrics_stream(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    interval_ms: Option<u64>,
+) -> Result<u64, String> {
+    let interval_ms = match interval_ms {
+        Some(ms) => ms,
+        None => state.config.lock().await.metrics_interval_ms,
+    }
+    .clamp(500, 300_000);
+
+    let (id, cancel) = state.streams.lock().await.start(StreamKind::Metrics);
+    spawn_stream(
+        state.inner().clone(),
+        id,
+        run_metrics_stream(app, state.inner().clone(), cancel, interval_ms),
+    );
+    Ok(id)
+}
+
+/// Stop the metrics stream started by `start_metrics_stream`
+#[tauri::command]
+async fn stop_metrics_stream(state: tauri::State<'_, Arc<AppState>>) -> Result<(), String> {
+    state.streams.lock().await.cancel(Some(StreamKind::Metrics));
+    Ok(())
+}
+
+/// Stop every background stream (log, health, ...), e.g. when the UI
+/// navigates away. Returns how many were running.
+#[tauri::command]
+async fn cancel_all_streams(state: tauri::State<'_, Arc<AppState>>) -> Result<usize, String> {
+    let cancelled = state.streams.lock().await.cancel(None);
+    if cancelled > 0 {
+        info!("Cancelled {} background stream(s)", cancelled);
+    }
+    Ok(cancelled)
+}
+
+/// Resolve `path` against the backend `base` URL, refusing anything that
+/// would leave the backend host and port
+fn proxy_url(base: &str, path: &str) -> Result<reqwest::Url, String> {
+    if !path.starts_with('/') || path.starts_with("//") {
+        return Err(format!(
+            "Backend path must start with a single '/': {}",
+            path
+        ));
+    }
+    let base = reqwest::Url::parse(base).map_err(|e| format!("Invalid backend base URL: {}", e))?;
+    let url = base
+        .join(path)
+        .map_err(|e| format!("Invalid backend path {}: {}", path, e))?;
+    if url.scheme() != base.scheme()
+        || url.host() != base.host()
+        || url.port_or_known_default() != base.port_or_known_default()
+    {
+        return Err(format!("Refusing to proxy outside the backend: {}", path));
+    }
+    Ok(url)
+}
+
+#[derive(serde::Serialize)]
+struct ProxyResponse {
+    status: u16,
+    headers: HashMap<String, String>,
+    body: String,
+}
+
+/// Forward a request to the backend API, avoiding CORS in the webview.
+///
+/// `path` is resolved against the backend base URL and must stay on the
+/// backend host and port. At most `proxy_concurrency` requests run at once;
+/// further calls queue until a slot frees up.
+#[tauri::command]
+async fn backend_request(
+    state: tauri::State<'_, Arc<AppState>>,
+    method: String,
+    path: String,
+    body: Option<serde_json::Value>,
+    headers: Option<HashMap<String, String>>,
+) -> Result<ProxyResponse, String> {
+    let method = reqwest::Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+        .map_err(|_| format!("Invalid HTTP method: {}", method))?;
+    let url = proxy_url(&state.backend_base_url(), &path)?;
+
+    state.proxy_queued.fetch_add(1, Ordering::SeqCst);
+    let permit = state.proxy_limiter.acquire().await;
+    state.proxy_queued.fetch_sub(1, Ordering::SeqCst);
+    let _permit = permit.map_err(|e| format!("Proxy limiter closed: {}", e))?;
+    state.proxy_requests.fetch_add(1, Ordering::SeqCst);
+
+    let mut request = state.http_client.request(method, url);
+    for (name, value) in headers.unwrap_or_default() {
+        request = request.header(name, value);
+    }
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+
+    let response = request.send().await.map_err(|e| {
+        state.proxy_errors.fetch_add(1, Ordering::SeqCst);
+        format!("Backend request failed: {}", e)
+    })?;
+    let status = response.status().as_u16();
+    let headers = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect();
+    let body = response.text().await.map_err(|e| {
+        state.proxy_errors.fetch_add(1, Ordering::SeqCst);
+        format!("Failed to read backend response: {}", e)
+    })?;
+
+    Ok(ProxyResponse {
+        status,
+        headers,
+        body,
+    })
+}
+
+#[derive(serde::Serialize)]
+struct PortProbe {
+    port: u16,
+    /// Something is bound to the port (we could connect, or could not bind)
+    in_use: bool,
+    /// A TCP connection to the port was accepted
+    responded: bool,
+    /// Listening process, when the platform lets us find it
+    owner: Option<PortOwner>,
+}
+
+/// Wait until `port` (default: the backend port) can be bound again, up to
+/// `timeout_ms` (default `PORT_RELEASE_TIMEOUT_SECS`).
+///
+/// Emits `backend-port-wait` while waiting; returns whether it is free.
+#[tauri::command]
+async fn wait_for_port_free(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    port: Option<u16>,
+    timeout_ms: Option<u64>,
+) -> Result<bool, String> {
+    let port = port.unwrap_or_else(|| state.backend_port.load(Ordering::SeqCst));
+    let timeout = Duration::from_millis(
+        timeout_ms
+            .unwrap_or(PORT_RELEASE_TIMEOUT_SECS * 1000)
+            .min(60_000),
+    );
+    Ok(wait_for_port_release(&app, port, timeout).await)
+}
+
+/// Check whether a local port is taken and, best-effort, by which process
+#[tauri::command]
+async fn probe_port(port: u16) -> Result<PortProbe, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+        let responded = std::net::TcpStream::connect_timeout(
+            &addr,
+            Duration::from_millis(PORT_PROBE_TIMEOUT_MS),
+        )
+        .is_ok();
+        let bind_blocked = matches!(
+            std::net::TcpListener::bind(addr),
+            Err(e) if e.kind() == std::io::ErrorKind::AddrInUse
+        );
+        let in_use = responded || bind_blocked;
+        PortProbe {
+            port,
+            in_use,
+            responded,
+            owner: if in_use { find_port_owner(port) } else { None },
+        }
+    })
+    .await
+    .map_err(|e| format!("Port probe failed: {}", e))
+}
+
+#[derive(serde::Serialize)]
+struct SelftestCheck {
+    path: String,
+    ok: bool,
+    status: Option<u16>,
+    latency_ms: u64,
+    error: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct SelftestReport {
+    passed: bool,
+    checks: Vec<SelftestCheck>,
+    /// Body of the backend's own `/api/selftest`, when it provides one
+    backend_report: Option<serde_json::Value>,
+    /// Verdict of `/api/selftest`; `None` when the backend doesn't provide it
+    backend_passed: Option<bool>,
+    total_ms: u64,
+}
+
+/// Verdict in a `/api/selftest` body: a boolean `passed` or `ok` field, or
+/// a `status` of `ok`/`pass`/`passed`. A body without one passes, since the
+/// endpoint already answered with a success status.
+fn selftest_body_passed(body: &serde_json::Value) -> bool {
+    for key in ["passed", "ok"] {
+        if let Some(passed) = body.get(key).and_then(serde_json::Value::as_bool) {
+            return passed;
+        }
+    }
+    match body.get("status").and_then(serde_json::Value::as_str) {
+        Some(status) => ["ok", "pass", "passed"].contains(&status.to_ascii_lowercase().as_str()),
+        None => true,
+    }
+}
+
+async fn run_selftest_check(client: reqwest::Client, url: String, path: String) -> SelftestCheck {
+    let start = std::time::Instant::now();
+    let result = client
+        .get(&url)
+        .timeout(Duration::from_secs(SELFTEST_CHECK_TIMEOUT_SECS))
+        .send()
+        .await;
+    let latency_ms = start.elapsed().as_millis() as u64;
+    match result {
+        Ok(response) => {
+            let status = response.status();
+            SelftestCheck {
+                path,
+                ok: status.is_success(),
+                status: Some(status.as_u16()),
+                latency_ms,
+                error: (!status.is_success()).then(|| format!("HTTP {}", status)),
+            }
+        }
+        Err(e) => SelftestCheck {
+            path,
+            ok: false,
+            status: None,
+            latency_ms,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// Exercise the configured backend endpoints concurrently and report per-check results.
+///
+/// The run only passes if the backend's own `/api/selftest` (when it has
+/// one) passes as well.
+#[tauri::command]
+async fn run_backend_selftest(
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<SelftestReport, String> {
+    let endpoints = state.config.lock().await.selftest_endpoints.clone();
+    let base_url = state.backend_base_url();
+    let client = state.http_client.clone();
+    let start = std::time::Instant::now();
+
+    let handles = endpoints
+        .into_iter()
+        .map(|path| {
+            let url = format!("{}{}", base_url, path);
+            tauri::async_runtime::spawn(run_selftest_check(client.clone(), url, path))
+        })
+        .collect::<Vec<_>>();
+
+    let dedicated = client
+        .get(format!("{}{}", base_url, SELFTEST_DEDICATED_PATH))
+        .timeout(Duration::from_secs(SELFTEST_CHECK_TIMEOUT_SECS))
+        .send()
+        .await;
+    let (backend_report, backend_passed) = match dedicated {
+        Ok(response) if response.status() == reqwest::StatusCode::NOT_FOUND => (None, None),
+        Ok(response) if response.status().is_success() => {
+            let body = response.json::<serde_json::Value>().await.ok();
+            let passed = body.as_ref().map_or(true, selftest_body_passed);
+            (body, Some(passed))
+        }
+        Ok(response) => {
+            warn!("Backend self-test answered {}", response.status());
+            (None, Some(false))
+        }
+        Err(e) => {
+            warn!("Backend self-test failed: {}", e);
+            (None, Some(false))
+        }
+    };
+
+    let mut checks = Vec::with_capacity(handles.len());
+    for handle in handles {
+        checks.push(
+            handle
+                .await
+                .map_err(|e| format!("Self-test check panicked: {}", e))?,
+        );
+    }
+
+    Ok(SelftestReport {
+        passed: checks.iter().all(|check| check.ok) && backend_passed != Some(false),
+        checks,
+        backend_report,
+        backend_passed,
+        total_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scratch directory under the system temp dir, removed on drop so a
+    /// failed assertion does not leave it behind
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("alproj-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+    }
+
+    impl std::ops::Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_is_dev_mode() {
+        // In debug builds, this should return true
+        #[cfg(debug_assertions)]
+        assert!(is_dev_mode());
+
+        // In release builds, this should return false
+        #[cfg(not(debug_assertions))]
+        assert!(!is_dev_mode());
+    }
+
+    #[test]
+    fn test_uvicorn_module_files() {
+        let files = uvicorn_module_files("src.app.main:app").unwrap();
+        assert_eq!(files[0], Path::new("src/app/main.py"));
+        assert_eq!(files[1], Path::new("src/app/main/__init__.py"));
+        assert_eq!(
+            uvicorn_module_files("main:app").unwrap()[0],
+            Path::new("main.py")
+        );
+        assert!(uvicorn_module_files("app.main").is_err());
+        assert!(uvicorn_module_files("app..main:app").is_err());
+        assert!(uvicorn_module_files("app.main:").is_err());
+    }
+
+    #[test]
+    fn test_error_category_from_log() {
+        assert_eq!(
+            ErrorCategory::from_log("ERROR: [Errno 98] error while attempting to bind on address ('127.0.0.1', 8765): address already in use"),
+            Some(ErrorCategory::AddressInUse)
+        );
+        assert_eq!(
+            ErrorCategory::from_log("ERROR: [Errno 99] error while attempting to bind on address ('10.0.0.9', 8765): cannot assign requested address"),
+            Some(ErrorCategory::AddressNotAvailable)
+        );
+        assert_eq!(
+            ErrorCategory::from_log("ERROR: [Errno 13] error while attempting to bind on address ('127.0.0.1', 80): permission denied"),
+            Some(ErrorCategory::PermissionDenied)
+        );
+        assert_eq!(
+            ErrorCategory::from_log("PermissionError: [Errno 13] Permission denied: 'cache'"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_status_codes() {
+        assert_eq!(parse_status_codes("2xx"), Some(vec![200..=299]));
+        assert_eq!(
+            parse_status_codes("200, 204,300-302"),
+            Some(vec![200..=200, 204..=204, 300..=302])
+        );
+        assert_eq!(parse_status_codes("ok"), None);
+        assert_eq!(parse_status_codes("299-200"), None);
+        assert_eq!(parse_status_codes("9xx"), None);
+        assert_eq!(parse_status_codes("700xx"), None);
+        assert_eq!(parse_status_codes("999xx"), None);
+        assert_eq!(parse_status_codes(" , "), None);
+    }
+
+    #[test]
+    fn test_identity_marker_parse_and_match() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+
+        let body = IdentityMarker::parse(" app = alproj").unwrap();
+        assert_eq!(
+            body,
+            IdentityMarker::BodyField {
+                key: "app".to_string(),
+                value: "alproj".to_string()
+            }
+        );
+        let header = IdentityMarker::parse("header:X-App=alproj").unwrap();
+        assert_eq!(
+            header,
+            IdentityMarker::Header {
+                name: "X-App".to_string(),
+                value: "alproj".to_string()
+            }
+        );
+        assert_eq!(header.to_string(), "header:X-App=alproj");
+        assert_eq!(IdentityMarker::parse("alproj"), None);
+        assert_eq!(IdentityMarker::parse("=alproj"), None);
+        assert_eq!(IdentityMarker::parse("header: =alproj"), None);
+
+        let mut headers = HeaderMap::new();
+        assert!(body.matches(&headers, br#"{"app":"alproj","status":"ok"}"#));
+        assert!(!body.matches(&headers, br#"{"app":"other"}"#));
+        assert!(!body.matches(&headers, b"not json"));
+        let numeric = IdentityMarker::parse("api=2").unwrap();
+        assert!(numeric.matches(&headers, br#"{"api":2}"#));
+
+        assert!(!header.matches(&headers, b""));
+        headers.insert("x-app", HeaderValue::from_static("alproj"));
+        assert!(header.matches(&headers, b""));
+        headers.insert("x-app", HeaderValue::from_static("other"));
+        assert!(!header.matches(&headers, b""));
+    }
+
+    #[test]
+    fn test_read_log_chunk_text_holds_back_split_tails() {
+        let data = "aé€😀b".repeat(50);
+        let (text, consumed) =
+            read_log_chunk_text(&mut data.as_bytes(), data.len(), false).unwrap();
+        assert_eq!(text, data);
+        assert_eq!(consumed, data.len());
+
+        // A character cut by `to_read` is left for the next read
+        let (text, consumed) = read_log_chunk_text(&mut data.as_bytes(), 2, false).unwrap();
+        assert_eq!(text, "a");
+        assert_eq!(consumed, 1);
+
+        // Characters and escape sequences split across buffer fills are
+        // carried into the next piece
+        let data = format!(
+            "{}\u{1b}[31mé{}",
+            "x".repeat(LOG_READ_BUFFER_BYTES - 2),
+            "y"
+        );
+        let (text, consumed) = read_log_chunk_text(&mut data.as_bytes(), data.len(), true).unwrap();
+        assert_eq!(text, format!("{}éy", "x".repeat(LOG_READ_BUFFER_BYTES - 2)));
+        assert_eq!(consumed, data.len());
+
+        // An unfinished escape sequence is measured in raw bytes, even when
+        // followed by invalid UTF-8 that decodes to longer replacement chars
+        let data = b"ok\x1b[\xff\xfe";
+        let (text, consumed) = read_log_chunk_text(&mut &data[..], data.len(), true).unwrap();
+        assert_eq!(text, "ok");
+        assert_eq!(consumed, 2);
+    }
+
+    #[test]
+    fn test_rotate_log_if_large_shifts_old_logs() {
+        let dir = TempDir::new("log-rotate");
+        let log_path = dir.join(BACKEND_STDOUT_LOG_FILE_NAME);
+
+        fs::write(&log_path, "small").unwrap();
+        assert!(!rotate_log_if_large(&log_path, 10, 2).unwrap());
+
+        for run in ["first run", "second run", "third run"] {
+            fs::write(&log_path, run).unwrap();
+            assert!(rotate_log_if_large(&log_path, 5, 2).unwrap());
+            assert!(!log_path.exists());
+        }
+        let read = |n| fs::read_to_string(rotated_log_path(&log_path, n)).unwrap();
+        assert_eq!(read(1), "third run");
+        assert_eq!(read(2), "second run");
+        assert!(!rotated_log_path(&log_path, 3).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_backend_output_streams_go_to_separate_logs() {
+        let dir = TempDir::new("log-streams");
+        let stdout_path = dir.join(BACKEND_STDOUT_LOG_FILE_NAME);
+        let stderr_path = dir.join(BACKEND_STDERR_LOG_FILE_NAME);
+
+        let status = Command::new("sh")
+            .args(["-c", "echo started; echo Traceback >&2; echo done"])
+            .stdout(Stdio::from(open_backend_log(&stdout_path).unwrap()))
+            .stderr(Stdio::from(open_backend_log(&stderr_path).unwrap()))
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        assert_eq!(fs::read_to_string(&stdout_path).unwrap(), "started\ndone\n");
+        assert_eq!(fs::read_to_string(&stderr_path).unwrap(), "Traceback\n");
+    }
+
+    #[test]
+    fn test_line_index_extends_and_rebuilds() {
+        let dir = TempDir::new("line-index");
+        let log_path = dir.join(BACKEND_STDOUT_LOG_FILE_NAME);
+        let mut index = LineIndex::default();
+
+        fs::write(&log_path, "one\ntwo\nthr").unwrap();
+        index.update(&log_path).unwrap();
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.seek_point(1), Some((0, 1)));
+
+        // Appended output extends the partial last line
+        let mut file = OpenOptions::new().append(true).open(&log_path).unwrap();
+        std::io::Write::write_all(&mut file, b"ee\nfour\n").unwrap();
+        index.update(&log_path).unwrap();
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(index.seek_point(4), None);
+        let (offset, skip) = index.seek_point(2).unwrap();
+        let mut file = fs::File::open(&log_path).unwrap();
+        file.seek(SeekFrom::Start(offset)).unwrap();
+        assert_eq!(
+            read_lines_after(file, skip, 5).unwrap(),
+            [b"three".to_vec(), b"four".to_vec()]
+        );
+
+        // Only every `LOG_INDEX_STRIDE`th line start is kept
+        let text: String = (0..LOG_INDEX_STRIDE * 3)
+            .map(|n| format!("line {}\r\n", n))
+            .collect();
+        fs::write(&log_path, &text).unwrap();
+        index.update(&log_path).unwrap();
+        assert_eq!(index.line_count(), LOG_INDEX_STRIDE * 3);
+        assert_eq!(index.checkpoints.len(), 4);
+        let line = LOG_INDEX_STRIDE * 2 + 5;
+        let (offset, skip) = index.seek_point(line).unwrap();
+        assert_eq!(skip, 5);
+        let lines = read_lines_after(&text.as_bytes()[offset as usize..], skip, 1).unwrap();
+        assert_eq!(lines, [format!("line {}", line).into_bytes()]);
+
+        // A truncated or replaced log is indexed from scratch
+        fs::write(&log_path, "new\n").unwrap();
+        index.update(&log_path).unwrap();
+        assert_eq!(index.line_count(), 1);
+        fs::write(&log_path, "other log\nline\nline\n").unwrap();
+        index.update(&log_path).unwrap();
+        assert_eq!(index.line_count(), 3);
+    }
+
+    #[test]
+    fn test_latency_trend_reports_slope_and_degradation() {
+        let mut trend = LatencyTrend::default();
+        for latency in [10, 10, 10] {
+            trend.record(latency);
+            assert!(trend.check_degraded(3).is_none());
+        }
+        let summary = trend.summary().unwrap();
+        assert_eq!(summary.baseline_ms, Some(10.0));
+        assert_eq!(summary.trend_ms_per_min, 0.0);
+
+        // Climbing latency: positive slope, and the floor-raised baseline
+        // (20 ms * 3) is crossed exactly once
+        let mut crossings = 0;
+        for latency in (1..=12).map(|i| i * 20) {
+            trend.record(latency);
+            crossings += trend.check_degraded(3).is_some() as u32;
+        }
+        let summary = trend.summary().unwrap();
+        assert_eq!(summary.samples, LATENCY_WINDOW);
+        assert!(summary.trend_ms_per_min > 0.0);
+        assert_eq!(crossings, 1);
+    }
+
+    #[test]
+    fn test_format_utc_timestamp() {
+        assert_eq!(format_utc_timestamp(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(
+            format_utc_timestamp(951_782_400_000),
+            "2000-02-29 00:00:00 UTC"
+        );
+        assert_eq!(
+            format_utc_timestamp(1_700_000_000_999),
+            "2023-11-14 22:13:20 UTC"
+        );
+    }
+
+    #[test]
+    #[ignore = "needs python3 on PATH; run with `cargo test -- --ignored`"]
+    fn test_unbuffered_child_output_reaches_log_promptly() {
+        let dir = TempDir::new("unbuffered");
+        let log_path = dir.join(BACKEND_STDOUT_LOG_FILE_NAME);
+        let stdout_log = open_backend_log(&log_path).unwrap();
+        let stderr_log = open_backend_log(&dir.join(BACKEND_STDERR_LOG_FILE_NAME)).unwrap();
+
+        // Prints, then stays alive: buffered output would only show up at exit
+        let mut command = Command::new("python3");
+        command
+            .args(["-c", "import time; print('ready'); time.sleep(10)"])
+            .stdout(Stdio::from(stdout_log))
+            .stderr(Stdio::from(stderr_log))
+            // Only the setting under test may make the child unbuffered
+            .env_remove("PYTHONUNBUFFERED");
+        apply_backend_env(&mut command, &BackendConfig::default());
+        let mut child = command
+            .spawn()
+            .expect("python3 is required to run this test");
+
+        let start = std::time::Instant::now();
+        let mut seen = false;
+        while start.elapsed() < Duration::from_secs(5) {
+            if fs::read_to_string(&log_path)
+                .unwrap_or_default()
+                .contains("ready")
+            {
+                seen = true;
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        let _ = child.kill();
+        let _ = child.wait();
+        assert!(
+            seen,
+            "child output did not reach the log while it was running"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shutdown_waits_for_graceful_exit_then_kills() {
+        tauri::async_runtime::block_on(async {
+            // Exits on SIGTERM, so the grace period is cut short
+            let child = Command::new("sleep").arg("30").spawn().unwrap();
+            let start = std::time::Instant::now();
+            ProcessHandle::StdChild(child)
+                .shutdown(Duration::from_secs(5))
+                .await
+                .unwrap();
+            assert!(start.elapsed() < Duration::from_secs(2));
+
+            // Ignores SIGTERM, so it is killed once the grace period is over
+            let child = Command::new("sh")
+                .args(["-c", "trap '' TERM; sleep 30"])
+                .spawn()
+                .unwrap();
+            // Give the shell time to install the trap
+            sleep(Duration::from_millis(200)).await;
+            let start = std::time::Instant::now();
+            ProcessHandle::StdChild(child)
+                .shutdown(Duration::from_millis(500))
+                .await
+                .unwrap();
+            assert!(start.elapsed() >= Duration::from_millis(500));
+        });
+    }
+
+    #[test]
+    fn test_parse_env_file_keeps_spaces_and_equals_in_values() {
+        let vars = parse_env_file(
+            "# comment\n\nDATABASE_URL=postgres://u:p@host/db?opt=a=b\nexport ALPROJ_DATA_DIR = \"/data/my projects\"\nNO_EQUALS\nBAD KEY=1\nEMPTY=\n",
+        );
+        assert_eq!(vars["DATABASE_URL"], "postgres://u:p@host/db?opt=a=b");
+        assert_eq!(vars["ALPROJ_DATA_DIR"], "/data/my projects");
+        assert_eq!(vars["EMPTY"], "");
+        assert_eq!(vars.len(), 3);
+        assert!(is_reserved_backend_env("Path"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ensure_executable_restores_execute_bits() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new("exec");
+        let path = dir.join("backend-sidecar-test");
+        fs::write(&path, b"#!/bin/sh\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        ensure_executable(&path).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[test]
+    fn test_verify_sidecar_checksum() {
+        let dir = TempDir::new("checksum");
+        let binary = dir.join("backend-sidecar-test");
+        fs::write(&binary, b"sidecar").unwrap();
+
+        // Without a checksum file the binary is let through
+        assert!(verify_sidecar_checksum(&dir, &binary).is_ok());
+
+        let hash = sha256_file(&binary).unwrap();
+        fs::write(
+            dir.join(SIDECAR_CHECKSUM_FILE_NAME),
+            format!("{}  backend-sidecar-test\n", hash.to_uppercase()),
+        )
+        .unwrap();
+        assert!(verify_sidecar_checksum(&dir, &binary).is_ok());
+
+        fs::write(&binary, b"tampered").unwrap();
+        let error = verify_sidecar_checksum(&dir, &binary).unwrap_err();
+        assert!(error.contains(&hash.to_uppercase()));
+        assert!(error.contains(&sha256_file(&binary).unwrap()));
+    }
+
+    #[test]
+    fn test_sidecar_names_for_supported_triples() {
+        let cases = [
+            ("aarch64", "macos", "gnu", "aarch64-apple-darwin"),
+            ("x86_64", "macos", "gnu", "x86_64-apple-darwin"),
+            ("aarch64", "linux", "gnu", "aarch64-unknown-linux-gnu"),
+            ("x86_64", "linux", "gnu", "x86_64-unknown-linux-gnu"),
+            ("x86_64", "windows", "msvc", "x86_64-pc-windows-msvc"),
+            ("aarch64", "windows", "msvc", "aarch64-pc-windows-msvc"),
+        ];
+        for (arch, os, env, triple) in cases {
+            assert_eq!(target_triple(arch, os, env), triple);
+            assert!(SIDECAR_TARGET_TRIPLES.contains(&triple));
+        }
+        assert_eq!(
+            sidecar_dir_name("x86_64-unknown-linux-gnu"),
+            "sidecar-x86_64-unknown-linux-gnu"
+        );
+        assert_eq!(
+            sidecar_binary_name("aarch64-apple-darwin"),
+            "backend-sidecar-aarch64-apple-darwin"
+        );
+        assert_eq!(
+            sidecar_binary_name("x86_64-pc-windows-msvc"),
+            "backend-sidecar-x86_64-pc-windows-msvc.exe"
+        );
+        assert_eq!(
+            sidecar_dir_name("aarch64-pc-windows-msvc"),
+            "sidecar-aarch64-pc-windows-msvc"
+        );
+        assert_eq!(
+            sidecar_binary_name("aarch64-pc-windows-msvc"),
+            "backend-sidecar-aarch64-pc-windows-msvc.exe"
+        );
+        assert!(
+            !SIDECAR_TARGET_TRIPLES.contains(&target_triple("x86_64", "linux", "musl").as_str())
+        );
+    }
+
+    #[test]
+    fn test_collect_descendants_from_survives_cycles() {
+        // 1 -> 2 -> 3 -> 2 is a cycle; 4 is listed twice under 1
+        let parents = [(2, 1), (3, 2), (2, 3), (4, 1), (4, 1), (5, 4), (1, 5)];
+        let mut visited = HashSet::from([1]);
+        let mut descendants = Vec::new();
+        collect_descendants_from(&parents, 1, &mut visited, &mut descendants);
+        assert_eq!(descendants, vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_startup_health_urls_use_configured_pieces() {
+        let state = AppState::new(BackendConfig {
+            scheme: "https".to_string(),
+            host: "localhost".to_string(),
+            health_path: "/healthz".to_string(),
+            ..BackendConfig::default()
+        });
+        assert_eq!(
+            state.startup_health_urls(),
+            vec![
+                format!("https://localhost:{}/healthz", BACKEND_PORT),
+                format!("https://127.0.0.1:{}/healthz", BACKEND_PORT),
+            ]
+        );
+
+        let state = AppState::new(BackendConfig {
+            host: "backend.local".to_string(),
+            ..BackendConfig::default()
+        });
+        assert_eq!(state.startup_health_urls().len(), 1);
+
+        // Behind a TLS terminator the URL port is not the bind port
+        let state = AppState::new(BackendConfig {
+            scheme: "https".to_string(),
+            host: "backend.local".to_string(),
+            url_port: Some(8443),
+            ..BackendConfig::default()
+        });
+        assert_eq!(
+            state.startup_health_urls(),
+            vec!["https://backend.local:8443/api/health".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_config_file_values_apply() {
+        let dir = TempDir::new("config");
+        assert!(read_config_file(&dir).is_none());
+
+        fs::write(
+            dir.join(CONFIG_FILE_NAME),
+            "port = 9000\nhealth_path = \"/healthz\"\nlauncher = \"uv\"\n\n[env]\nFOO = \"bar\"\n",
+        )
+        .unwrap();
+        let mut config = BackendConfig::default();
+        config.apply_file(read_config_file(&dir).unwrap());
+        assert_eq!(config.port, 9000);
+        assert_eq!(config.health_path, "/healthz");
+        assert_eq!(config.launcher, Launcher::Uv);
+        assert_eq!(
+            config.env_file_vars.get("FOO").map(String::as_str),
+            Some("bar")
+        );
+        assert_eq!(config.health_timeout_secs, HEALTH_CHECK_TIMEOUT_SECS);
+
+        fs::write(dir.join(CONFIG_FILE_NAME), "port = \"not a port\"").unwrap();
+        assert!(read_config_file(&dir).is_none());
+        // Misspelled keys are reported rather than silently ignored
+        fs::write(dir.join(CONFIG_FILE_NAME), "prot = 9000").unwrap();
+        assert!(read_config_file(&dir).is_none());
+        fs::write(
+            dir.join(CONFIG_FILE_NAME),
+            "[services.worker]\ncomand = [\"worker\"]\n",
+        )
+        .unwrap();
+        assert!(read_config_file(&dir).is_none());
+    }
+
+    #[test]
+    fn test_config_file_services_are_validated() {
+        let file: FileConfig = toml::from_str(
+            r#"
+            [services.worker]
+            command = ["python", "-m", "app.worker"]
+            health_url = "http://127.0.0.1:8766/health"
+
+            [services.main]
+            command = ["python"]
+
+            [services."bad/name"]
+            command = ["python"]
+
+            [services.empty]
+            command = []
+            "#,
+        )
+        .unwrap();
+        let mut config = BackendConfig::default();
+        config.apply_file(file);
+        assert_eq!(config.services.len(), 1);
+        let worker = &config.services["worker"];
+        assert_eq!(worker.command, ["python", "-m", "app.worker"]);
+        assert_eq!(
+            worker.health_url.as_deref(),
+            Some("http://127.0.0.1:8766/health")
+        );
+
+        let service = ServiceConfig {
+            command: vec!["./worker".to_string()],
+            health_url: Some("not a url".to_string()),
+        };
+        assert!(validate_service("worker", &service).is_err());
+    }
+
+    #[test]
+    fn test_health_report_keeps_unknown_fields() {
+        let report: HealthReport = serde_json::from_str(
+            r#"{"status":"ok","app":"alproj","version":"0.1.0","uptime_secs":12}"#,
+        )
+        .unwrap();
+        assert_eq!(report.status, "ok");
+        assert_eq!(report.version.as_deref(), Some("0.1.0"));
+        assert_eq!(report.uptime_secs, Some(12));
+        assert_eq!(report.extra.get("app"), Some(&serde_json::json!("alproj")));
+
+        let report: HealthReport = serde_json::from_str(r#"{"app":"alproj"}"#).unwrap();
+        assert_eq!(report.status, "unknown");
+        assert_eq!(report.version, None);
+    }
+
+    #[test]
+    fn test_log_line_parse_recognizes_uvicorn_and_backend_formats() {
+        let line = LogLine::parse("\u{1b}[32mINFO\u{1b}[0m:     Application startup complete.");
+        assert_eq!(line.level, LogLevel::Info);
+        assert_eq!(line.message, "Application startup complete.");
+        assert_eq!(line.timestamp, None);
+
+        let line =
+            LogLine::parse("2026-01-05 10:00:00,123 - app.main - WARNING - Slow: 3 s - retrying");
+        assert_eq!(line.level, LogLevel::Warning);
+        assert_eq!(line.timestamp.as_deref(), Some("2026-01-05 10:00:00,123"));
+        assert_eq!(line.message, "Slow: 3 s - retrying");
+
+        assert_eq!(LogLine::parse("ERROR:root:boom").level, LogLevel::Error);
+        let line = LogLine::parse("  File \"main.py\", line 3, in <module>");
+        assert_eq!(line.level, LogLevel::Unknown);
+        assert_eq!(line.message, line.raw);
+    }
+
+    #[test]
+    fn test_utf8_complete_len_defers_split_character() {
+        let text = "log 日本".as_bytes();
+        assert_eq!(utf8_complete_len(text), text.len());
+        // Cut inside the last 3-byte character
+        assert_eq!(utf8_complete_len(&text[..text.len() - 1]), text.len() - 3);
+        assert_eq!(utf8_complete_len(&text[..text.len() - 2]), text.len() - 3);
+        assert_eq!(utf8_complete_len(b""), 0);
+    }
+
+    #[test]
+    fn test_strip_ansi_removes_colors_and_defers_split_sequences() {
+        let colored = "\u{1b}[32mINFO\u{1b}[0m:     Uvicorn running on http://127.0.0.1:8765";
+        assert_eq!(
+            strip_ansi(colored),
+            "INFO:     Uvicorn running on http://127.0.0.1:8765"
+        );
+        assert_eq!(strip_ansi("plain \u{1b}]0;title\u{07}text"), "plain text");
+
+        let split = b"done\n\x1b[3";
+        assert_eq!(ansi_complete_len(split), 5);
+        assert_eq!(ansi_complete_len(b"done\x1b[32m"), 9);
+        assert_eq!(ansi_complete_len(b"done\x1b"), 4);
+    }
+
+    #[test]
+    fn test_proxy_url_stays_on_backend() {
+        let base = "http://127.0.0.1:8765";
+        assert_eq!(
+            proxy_url(base, "/api/projects?id=1").unwrap().as_str(),
+            "http://127.0.0.1:8765/api/projects?id=1"
+        );
+        assert!(proxy_url(base, "api/projects").is_err());
+        assert!(proxy_url(base, "//example.com/x").is_err());
+        assert!(proxy_url(base, "/\\example.com/x").is_err());
+        assert!(proxy_url(base, "http://example.com/x").is_err());
+    }
+
+    #[test]
+    fn test_tail_lines_keeps_whole_multibyte_lines() {
+        let text = "起動中 ステップ 1\nステップ 2 完了 ✓\nÉchec: カメラ 🎥\n";
+        assert_eq!(
+            tail_lines(text, 2, 1000),
+            "ステップ 2 完了 ✓\nÉchec: カメラ 🎥"
+        );
+
+        // The budget fits the last line but not the one before it
+        let last = "Échec: カメラ 🎥";
+        let tail = tail_lines(text, 10, last.chars().count() + 5);
+        assert_eq!(tail, last);
+
+        // A single oversized line keeps its end on a character boundary
+        let tail = tail_lines("ログ🎥ログ🎥", 5, 3);
+        assert_eq!(tail, "ログ🎥");
+        assert_eq!(tail_lines("", 5, 10), "");
+    }
+
+    #[test]
+    fn test_search_log_context_and_truncation() {
+        let log = "start\nINFO: ok\n\x1b[31mERROR\x1b[0m: boom\nINFO: ok\nerror: again\nend\n";
+        let pattern = regex::RegexBuilder::new("error")
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+
+        let result = search_log(std::io::Cursor::new(log), &pattern, 1, 10).unwrap();
+        assert!(!result.truncated);
+        assert_eq!(result.matches.len(), 2);
+        let first = &result.matches[0];
+        assert_eq!(first.line_number, 2);
+        assert_eq!(first.offset, "start\nINFO: ok\n".len() as u64);
+        assert_eq!(first.line, "ERROR: boom");
+        assert_eq!(first.before, vec!["INFO: ok"]);
+        assert_eq!(first.after, vec!["INFO: ok"]);
+        assert_eq!(result.matches[1].after, vec!["end"]);
+
+        let result = search_log(std::io::Cursor::new(log), &pattern, 2, 1).unwrap();
+        assert!(result.truncated);
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.matches[0].after, vec!["INFO: ok", "error: again"]);
+    }
+
+    #[test]
+    fn test_redact_env_hides_secret_values() {
+        let vars = HashMap::from([
+            ("HF_TOKEN".to_string(), "hf_abc".to_string()),
+            ("api_key".to_string(), "k".to_string()),
+            ("DB_PASSWORD".to_string(), "p".to_string()),
+            ("ALPROJ_BACKEND_PORT".to_string(), "8765".to_string()),
+        ]);
+        let redacted = redact_env(&vars);
+        assert_eq!(redacted["HF_TOKEN"], "<redacted>");
+        assert_eq!(redacted["api_key"], "<redacted>");
+        assert_eq!(redacted["DB_PASSWORD"], "<redacted>");
+        assert_eq!(redacted["ALPROJ_BACKEND_PORT"], "8765");
+
+        let config = BackendConfig {
+            extra_env: vars.clone(),
+            env_file_vars: vars,
+            ..BackendConfig::default()
+        };
+        let dump = format!("{:#?}", redacted_config(&config));
+        assert!(!dump.contains("hf_abc"));
+        assert!(dump.contains("<redacted>"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exit_signal_names_common_signals() {
+        use std::os::unix::process::ExitStatusExt;
+        // Raw wait statuses: low bits hold the signal, the next byte the code
+        let killed = std::process::ExitStatus::from_raw(9);
+        assert_eq!(exit_signal(&killed).as_deref(), Some("SIGKILL"));
+        assert_eq!(
+            exit_signal(&std::process::ExitStatus::from_raw(10)).as_deref(),
+            Some("signal 10")
+        );
+        assert_eq!(
+            exit_signal(&std::process::ExitStatus::from_raw(1 << 8)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_launcher_order() {
+        assert_eq!(
+            parse_launcher_order("uv, python"),
+            Some(vec![Launcher::Uv, Launcher::Python])
+        );
+        assert_eq!(parse_launcher_order("uv"), Some(vec![Launcher::Uv]));
+        assert_eq!(parse_launcher_order("uv,conda"), None);
+        assert_eq!(parse_launcher_order("auto,uv"), None);
+        assert_eq!(parse_launcher_order(" , "), None);
+    }
+
+    #[test]
+    fn test_read_log_at_restarts_after_truncation() {
+        let dir = TempDir::new("log-rotated");
+        let path = dir.join(BACKEND_STDOUT_LOG_FILE_NAME);
+        fs::write(&path, "first run\nmore\n").unwrap();
+        let (start, text, consumed) = read_log_at(&path, 6, 1024, false).unwrap();
+        assert_eq!((start, text.as_str(), consumed), (6, "run\nmore\n", 9));
+
+        fs::write(&path, "new\n").unwrap();
+        let (start, text, consumed) = read_log_at(&path, 15, 1024, false).unwrap();
+        assert_eq!((start, text.as_str(), consumed), (0, "new\n", 4));
+    }
+
+    #[test]
+    fn test_transient_spawn_errors() {
+        use std::io::{Error, ErrorKind};
+        assert!(is_transient_spawn_error(&Error::from(
+            ErrorKind::WouldBlock
+        )));
+        assert!(!is_transient_spawn_error(&Error::from(ErrorKind::NotFound)));
+        assert!(!is_transient_spawn_error(&Error::from(
+            ErrorKind::PermissionDenied
+        )));
+        #[cfg(unix)]
+        assert!(is_transient_spawn_error(&Error::from_raw_os_error(26)));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_percent_and_is_reproducible() {
+        let mut rng = JitterRng::new();
+        let delays: Vec<u64> = (0..200)
+            .map(|_| rng.jitter(HEALTH_CHECK_MAX_INTERVAL_MS, HEALTH_CHECK_JITTER_PERCENT))
+            .collect();
+        assert!(delays.iter().all(|delay| (1600..=2400).contains(delay)));
+        assert!(delays
+            .iter()
+            .any(|delay| *delay != HEALTH_CHECK_MAX_INTERVAL_MS));
+
+        let mut again = JitterRng::new();
+        let first = again.jitter(HEALTH_CHECK_MAX_INTERVAL_MS, HEALTH_CHECK_JITTER_PERCENT);
+        assert_eq!(first, delays[0]);
+        assert_eq!(again.jitter(3, HEALTH_CHECK_JITTER_PERCENT), 3);
+    }
+
+    #[test]
+    fn test_log_streams_run_per_output_stream() {
+        let mut streams = StreamRegistry::default();
+        let (_, stdout) = streams.start(StreamKind::Log(OutputStream::Stdout));
+        let (_, stderr) = streams.start(StreamKind::Log(OutputStream::Stderr));
+        assert!(!stdout.load(Ordering::SeqCst));
+
+        let (_, replacement) = streams.start(StreamKind::Log(OutputStream::Stderr));
+        assert!(stderr.load(Ordering::SeqCst));
+        assert!(!replacement.load(Ordering::SeqCst));
+
+        // A finished task only takes its own entry with it
+        let (metrics, _) = streams.start(StreamKind::Metrics);
+        streams.finish(metrics);
+        streams.finish(metrics);
+        assert_eq!(streams.cancel(None), 2);
+    }
+
+    #[test]
+    fn test_reopen_if_replaced_follows_rotation() {
+        let dir = TempDir::new("log-reopen");
+        let path = dir.join(BACKEND_STDOUT_LOG_FILE_NAME);
+        fs::write(&path, "first run\n").unwrap();
+        let mut held = fs::File::open(&path).unwrap();
+        assert!(reopen_if_replaced(&mut held, &path, 10).is_none());
+
+        assert!(rotate_log_if_large(&path, 1, 3).unwrap());
+        fs::write(&path, "second\n").unwrap();
+        let mut current = reopen_if_replaced(&mut held, &path, 10).unwrap();
+        let mut text = String::new();
+        current.read_to_string(&mut text).unwrap();
+        assert_eq!(text, "second\n");
+    }
+
+    #[test]
+    fn test_startup_wait_extends_only_while_unanswered() {
+        let timeout = Duration::from_secs(HEALTH_CHECK_TIMEOUT_SECS);
+        let growing = Duration::ZERO;
+        let quiet = Duration::from_secs(STARTUP_QUIET_TIMEOUT_SECS);
+        let early = timeout - Duration::from_secs(1);
+        assert_eq!(startup_wait_end(early, timeout, quiet, true), None);
+
+        // Nothing answers yet and the log grows: keep waiting
+        assert_eq!(startup_wait_end(timeout, timeout, growing, false), None);
+        assert_eq!(
+            startup_wait_end(timeout * 2, timeout, quiet, false),
+            Some(StartupWaitEnd::Quiet)
+        );
+        let max = Duration::from_secs(MAX_HEALTH_CHECK_TIMEOUT_SECS);
+        assert_eq!(
+            startup_wait_end(max, timeout, growing, false),
+            Some(StartupWaitEnd::Limit)
+        );
+
+        // A backend answering 503 grows the log with access lines for our
+        // polls; that is not progress
+        assert_eq!(
+            startup_wait_end(timeout, timeout, growing, true),
+            Some(StartupWaitEnd::Timeout)
+        );
+    }
+
+    #[test]
+    fn test_old_fallback_logs_are_removed() {
+        let dir = TempDir::new("log-fallback");
+        let log_path = dir.join(BACKEND_STDOUT_LOG_FILE_NAME);
+        let stem = log_path.file_stem().unwrap().to_string_lossy().to_string();
+        for stamp in [1000, 3000, 2000] {
+            fs::write(dir.join(format!("{}-{}.log", stem, stamp)), "").unwrap();
+        }
+        fs::write(dir.join(format!("{}-notes.log", stem)), "").unwrap();
+
+        remove_old_fallback_logs(&log_path, 2);
+        assert!(!dir.join(format!("{}-1000.log", stem)).exists());
+        assert!(dir.join(format!("{}-2000.log", stem)).exists());
+        assert!(dir.join(format!("{}-3000.log", stem)).exists());
+        assert!(dir.join(format!("{}-notes.log", stem)).exists());
+    }
+
+    #[test]
+    fn test_event_journal_filters_and_evicts() {
+        let mut journal = EventJournal::default();
+        for n in 0..3 {
+            journal.record("backend-state", serde_json::json!(n));
+        }
+        let since = journal.since(1);
+        let ids: Vec<_> = since.events.iter().map(|entry| entry.id).collect();
+        assert_eq!(ids, [2, 3]);
+        assert_eq!(since.latest_id, 3);
+        assert!(!since.missed);
+        assert!(journal.since(3).events.is_empty());
+
+        for n in 3..EVENT_JOURNAL_CAPACITY + 10 {
+            journal.record("backend-state", serde_json::json!(n));
+        }
+        assert_eq!(journal.entries.len(), EVENT_JOURNAL_CAPACITY);
+        let since = journal.since(0);
+        assert!(since.missed);
+        assert_eq!(since.events[0].id, 11);
+        assert_eq!(since.latest_id, EVENT_JOURNAL_CAPACITY as u64 + 10);
+        assert!(!journal.since(10).missed);
+    }
+
+    #[test]
+    fn test_mock_backend_answers_health() {
+        use std::io::Write;
+
+        let mock = MockBackend::start(BACKEND_HOST, 0).unwrap();
+        assert_ne!(mock.port, 0);
+        let mut stream = std::net::TcpStream::connect((BACKEND_HOST, mock.port)).unwrap();
+        stream
+            .write_all(b"GET /api/health HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        mock.stop();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["app"], "alproj");
+        assert_eq!(body["mock"], true);
+    }
+
+    #[test]
+    fn test_verify_bundle_reports_missing_and_corrupted_files() {
+        let dir = TempDir::new("bundle");
+        assert!(verify_bundle(&dir).is_err());
+
+        fs::create_dir_all(dir.join("lib")).unwrap();
+        fs::write(dir.join("lib").join("good.so"), b"good").unwrap();
+        fs::write(dir.join("data.bin"), b"original").unwrap();
+        let good_hash = sha256_file(&dir.join("lib").join("good.so")).unwrap();
+        let data_hash = sha256_file(&dir.join("data.bin")).unwrap();
+        // Same size, different content
+        fs::write(dir.join("data.bin"), b"tampered").unwrap();
+        let manifest = serde_json::json!({
+            "files": [
+                { "path": "lib/good.so", "size": 4, "sha256": good_hash.to_uppercase() },
+                { "path": "data.bin", "size": 8, "sha256": data_hash },
+                { "path": "gone.txt", "size": 1, "sha256": good_hash },
+            ]
+        });
+        fs::write(dir.join(BUNDLE_MANIFEST_FILE_NAME), manifest.to_string()).unwrap();
+
+        let report = verify_bundle(&dir).unwrap();
+        assert_eq!(report.checked, 3);
+        assert_eq!(report.missing, ["gone.txt"]);
+        assert_eq!(report.corrupted, ["data.bin"]);
+        assert!(!report.ok);
+
+        assert!(looks_like_broken_bundle(
+            "Backend exited: ModuleNotFoundError: No module named 'uvicorn'"
+        ));
+        assert!(!looks_like_broken_bundle(
+            "Port 8765 is already in use by another process"
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extra_service_start_and_stop() {
+        let dir = TempDir::new("service");
+        let dirs = ServiceDirs {
+            work_dir: dir.to_path_buf(),
+            log_dir: dir.to_path_buf(),
+        };
+        let service = |script: &str| ServiceConfig {
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            health_url: None,
+        };
+        let state = AppState::new(BackendConfig {
+            services: HashMap::from([
+                ("worker".to_string(), service("echo up; exec sleep 30")),
+                ("oneshot".to_string(), service("exit 3")),
+            ]),
+            ..BackendConfig::default()
+        });
+        tauri::async_runtime::block_on(async {
+            assert!(start_extra_service(&state, &dirs, "missing").await.is_err());
+
+            let pid = start_extra_service(&state, &dirs, "worker").await.unwrap();
+            assert!(pid.is_some());
+            // Already running: the same process is reported
+            assert_eq!(
+                start_extra_service(&state, &dirs, "worker").await.unwrap(),
+                pid
+            );
+            let log_path = dir.join("service-worker.log");
+            let start = std::time::Instant::now();
+            while !fs::read_to_string(&log_path)
+                .unwrap_or_default()
+                .contains("up")
+            {
+                assert!(start.elapsed() < Duration::from_secs(5));
+                sleep(Duration::from_millis(20)).await;
+            }
+            assert!(stop_service_by_name(&state, "worker", Duration::from_secs(2)).await);
+            assert!(!stop_service_by_name(&state, "worker", Duration::from_secs(2)).await);
+
+            // A service that exited is started again rather than reported
+            let first = start_extra_service(&state, &dirs, "oneshot").await.unwrap();
+            let start = std::time::Instant::now();
+            while service_running(state.sidecars.lock().await.get_mut("oneshot").unwrap()) {
+                assert!(start.elapsed() < Duration::from_secs(5));
+                sleep(Duration::from_millis(20)).await;
+            }
+            let second = start_extra_service(&state, &dirs, "oneshot").await.unwrap();
+            assert_ne!(first, second);
+            stop_extra_services(&state, Duration::from_secs(2)).await;
+            assert!(state.sidecars.lock().await.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_selftest_body_verdict() {
+        use serde_json::json;
+
+        assert!(selftest_body_passed(&json!({ "passed": true })));
+        assert!(!selftest_body_passed(
+            &json!({ "passed": false, "status": "ok" })
+        ));
+        assert!(!selftest_body_passed(&json!({ "ok": false })));
+        assert!(selftest_body_passed(&json!({ "status": "OK" })));
+        assert!(!selftest_body_passed(&json!({ "status": "failed" })));
+        assert!(selftest_body_passed(&json!({ "checks": [] })));
     }
 }